@@ -1,11 +1,232 @@
-use crate::config::ConfigManager;
+use crate::config::{ConfigManager, ConfigOverrides};
+use crate::document::{self, DocumentMode, Segment};
+use crate::history::{self, HistoryEntry, HistoryFilter, HistoryMode};
+use crate::output::{self, OutputFormat, TranslationOutput};
+use crate::protect;
+use crate::resources::Resource;
 use crate::speech::SpeechManager;
-use crate::translator::Translator;
-use chrono::{DateTime, Utc};
+use crate::subtitles;
+use crate::translator::{self, Translator};
+use crate::vocabulary::{ExportFormat, Vocabulary};
+use colored::Colorize;
+use std::collections::HashMap;
 use std::error::Error;
-use std::fs::OpenOptions;
-use std::io::Write;
+use std::fs;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::Path;
 use std::sync::Arc;
+use windows::Win32::Storage::FileSystem::{GetFileType, FILE_TYPE_DISK, FILE_TYPE_PIPE};
+use windows::Win32::System::Console::{GetStdHandle, STD_INPUT_HANDLE};
+
+/// Commands recognized as the first non-option argument
+const COMMANDS: &[&str] = &[
+    "-h",
+    "--help",
+    "-c",
+    "--config",
+    "-v",
+    "--version",
+    "-s",
+    "--speech",
+    "-l",
+    "--languages",
+    "--export-vocab",
+    "--history",
+    "-q",
+];
+
+/// Parsed command line
+#[derive(Debug, Clone, PartialEq)]
+pub struct CliArgs {
+    pub command: Option<String>, // None = translate text
+    pub args: Vec<String>,       // Command arguments or words of the text
+    pub config_path: Option<String>,
+    pub overrides: ConfigOverrides,
+    pub output_format: OutputFormat,
+    pub document_mode: DocumentMode, // How --file and stdin text is split
+    pub input_file: Option<String>,  // --file
+    pub output_file: Option<String>, // --out
+    pub bilingual: bool,             // Keep original cue text above the translation
+    pub resource_file: Option<String>, // --i18n: .po or JSON locale file
+    pub read_stdin: bool,            // --stdin or "-": translate text from stdin
+}
+
+impl CliArgs {
+    /// Parse arguments (without program name)
+    /// Options may appear anywhere in the text, arguments after a command belong to it,
+    /// everything after "--" is literal text
+    pub fn parse(args: &[String]) -> Result<Self, Box<dyn Error>> {
+        let mut parsed = CliArgs {
+            command: None,
+            args: Vec::new(),
+            config_path: None,
+            overrides: ConfigOverrides::default(),
+            output_format: OutputFormat::Text,
+            document_mode: DocumentMode::Paragraphs,
+            input_file: None,
+            output_file: None,
+            bilingual: false,
+            resource_file: None,
+            read_stdin: false,
+        };
+
+        let mut i = 0;
+        while i < args.len() {
+            let arg = args[i].as_str();
+            i += 1;
+
+            if parsed.command.is_some() {
+                parsed.args.push(arg.to_string());
+                continue;
+            }
+
+            if arg == "--" {
+                parsed.args.extend(args[i..].iter().cloned());
+                break;
+            }
+
+            // "--name=value" or "--name value"
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value)),
+                _ => (arg, None),
+            };
+
+            match name {
+                "--from" | "--to" | "--provider" | "--format" | "--file" | "--markdown"
+                | "--subtitles" | "--out" | "--i18n" => {
+                    let value = match inline_value {
+                        Some(value) => value.to_string(),
+                        None => {
+                            let value = args
+                                .get(i)
+                                .ok_or_else(|| format!("Missing value for {}", name))?;
+                            i += 1;
+                            value.clone()
+                        }
+                    };
+
+                    if value.trim().is_empty() {
+                        return Err(format!("Empty value for {}", name).into());
+                    }
+
+                    match name {
+                        "--from" => parsed.overrides.source_language = Some(value),
+                        "--to" => parsed.overrides.target_language = Some(value),
+                        "--provider" => parsed.overrides.translate_provider = Some(value),
+                        "--file" => parsed.input_file = Some(value),
+                        "--markdown" => {
+                            parsed.input_file = Some(value);
+                            parsed.document_mode = DocumentMode::Markdown;
+                        }
+                        "--subtitles" => {
+                            parsed.input_file = Some(value);
+                            parsed.document_mode = DocumentMode::Subtitles;
+                        }
+                        "--out" => parsed.output_file = Some(value),
+                        "--i18n" => parsed.resource_file = Some(value),
+                        _ => parsed.output_format = OutputFormat::parse(&value)?,
+                    }
+                }
+                "--config" if inline_value.is_some() || Self::is_value(args.get(i)) => {
+                    let path = match inline_value {
+                        Some(value) => value.to_string(),
+                        None => {
+                            i += 1;
+                            args[i - 1].clone()
+                        }
+                    };
+
+                    // Never create a config file from a mistyped argument
+                    if !Path::new(&path).is_file() {
+                        return Err(format!("Config file not found: {}", path).into());
+                    }
+                    parsed.config_path = Some(path);
+                }
+                "--json" => parsed.output_format = OutputFormat::Json,
+                "--no-dict" => parsed.overrides.show_dictionary = Some(false),
+                "--no-clipboard" => parsed.overrides.copy_to_clipboard = Some(false),
+                "--lines" => parsed.document_mode = DocumentMode::Lines,
+                "--bilingual" => parsed.bilingual = true,
+                "--stdin" => parsed.read_stdin = true,
+                _ if parsed.args.is_empty() && COMMANDS.contains(&arg) => {
+                    parsed.command = Some(arg.to_string());
+                }
+                _ if Self::is_option(arg) => {
+                    return Err(format!(
+                        "Unknown option: {} (use 'tagent -- {}' to translate it as text)",
+                        arg, arg
+                    )
+                    .into());
+                }
+                _ => parsed.args.push(arg.to_string()),
+            }
+        }
+
+        if parsed.bilingual && parsed.document_mode != DocumentMode::Subtitles {
+            return Err("--bilingual can only be used with --subtitles".into());
+        }
+
+        if parsed.resource_file.is_some() && parsed.input_file.is_some() {
+            return Err("--i18n cannot be combined with --file, --markdown or --subtitles".into());
+        }
+
+        // "-" as the only text is the same as --stdin
+        if parsed.command.is_none() && parsed.args == ["-"] {
+            parsed.args.clear();
+            parsed.read_stdin = true;
+        }
+
+        if parsed.read_stdin
+            && (parsed.command.is_some()
+                || !parsed.args.is_empty()
+                || parsed.input_file.is_some()
+                || parsed.resource_file.is_some())
+        {
+            return Err("--stdin cannot be combined with text, commands, --file or --i18n".into());
+        }
+
+        // "tagent --config other.conf" shows that configuration
+        if parsed.command.is_none()
+            && parsed.args.is_empty()
+            && !parsed.read_stdin
+            && parsed.config_path.is_some()
+        {
+            parsed.command = Some("--config".to_string());
+        }
+
+        Ok(parsed)
+    }
+
+    /// Output format asked for on the command line, even if other arguments are invalid
+    /// Lets errors before and during initialization be reported as JSON
+    pub fn requested_format(args: &[String]) -> OutputFormat {
+        let mut format = OutputFormat::Text;
+        let mut args = args.iter().take_while(|arg| *arg != "--");
+
+        while let Some(arg) = args.next() {
+            let value = match arg.split_once('=') {
+                Some(("--format", value)) => Some(value.to_string()),
+                _ if arg == "--format" => args.next().cloned(),
+                _ if arg == "--json" => Some("json".to_string()),
+                _ => None,
+            };
+            if let Some(Ok(value)) = value.map(|value| OutputFormat::parse(&value)) {
+                format = value;
+            }
+        }
+
+        format
+    }
+
+    /// Option-like argument: "-x" or "--name", but not "-" or a negative number
+    fn is_option(arg: &str) -> bool {
+        arg.len() > 1 && arg.starts_with('-') && !arg[1..].starts_with(|c: char| c.is_ascii_digit())
+    }
+
+    fn is_value(arg: Option<&String>) -> bool {
+        arg.is_some_and(|arg| !Self::is_option(arg))
+    }
+}
 
 pub struct CliHandler {
     translator: Translator,
@@ -14,11 +235,19 @@ pub struct CliHandler {
 }
 
 impl CliHandler {
-    pub fn new() -> Result<Self, Box<dyn Error>> {
-        let translator = Translator::new()?;
-        let config_path = ConfigManager::get_default_config_path()?;
-        let config_manager = Arc::new(ConfigManager::new(config_path.to_string_lossy().as_ref())?);
-        let speech_manager = SpeechManager::new();
+    pub fn new(cli_args: &CliArgs) -> Result<Self, Box<dyn Error>> {
+        let config_path = match &cli_args.config_path {
+            Some(path) => path.clone(),
+            None => ConfigManager::get_default_config_path()?
+                .to_string_lossy()
+                .to_string(),
+        };
+        let config_manager = Arc::new(ConfigManager::with_overrides(
+            &config_path,
+            cli_args.overrides.clone(),
+        )?);
+        let translator = Translator::with_config_manager(config_manager.clone())?;
+        let speech_manager = SpeechManager::new(&config_manager.get_config())?;
 
         Ok(Self {
             translator,
@@ -27,38 +256,6 @@ impl CliHandler {
         })
     }
 
-    /// Save translation history to file (CLI version)
-    fn save_translation_history(
-        &self,
-        original: &str,
-        translated: &str,
-        source_lang: &str,
-        target_lang: &str,
-        config: &crate::config::Config,
-    ) -> Result<(), Box<dyn Error>> {
-        if !config.save_translation_history {
-            return Ok(()); // История отключена
-        }
-
-        let timestamp: DateTime<Utc> = Utc::now();
-        let formatted_time = timestamp.format("%Y-%m-%d %H:%M:%S UTC");
-
-        let entry = format!(
-            "[{}] {} -> {}\nIN:  {}\nOUT: {}\n---\n\n",
-            formatted_time, source_lang, target_lang, original, translated
-        );
-
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&config.history_file)?;
-
-        file.write_all(entry.as_bytes())?;
-        file.flush()?; // Принудительно записываем на диск
-
-        Ok(())
-    }
-
     /// Display CLI help information
     pub fn show_help() {
         ConfigManager::display_help();
@@ -76,15 +273,191 @@ impl CliHandler {
         self.config_manager.display_config()
     }
 
-    /// Process CLI arguments and determine action
-    pub async fn process_args(&self, args: Vec<String>) -> Result<(), Box<dyn Error>> {
-        if args.len() < 2 {
-            println!("Error: No arguments provided");
-            println!("Use --help for usage information");
+    /// Show languages supported by the translation provider
+    pub async fn show_languages(&self) -> Result<(), Box<dyn Error>> {
+        let languages = match self.translator.get_supported_languages().await {
+            Ok(languages) => languages,
+            Err(e) => {
+                eprintln!("Failed to get language list: {}", e);
+                return Err(e);
+            }
+        };
+
+        if languages.is_empty() {
+            println!(
+                "Language list is not available for {}",
+                self.translator.provider_name()
+            );
+            return Ok(());
+        }
+
+        println!("Languages supported by {}:", self.translator.provider_name());
+        for language in languages {
+            println!("  {:<8} {}", language.code, language.name);
+        }
+        println!();
+
+        Ok(())
+    }
+
+    /// Search translation history
+    /// args: [query] [--since DATE] [--until DATE] [--pair en:ru] [--mode MODE] [--limit N]
+    pub fn show_history(&self, args: &[String]) -> Result<(), Box<dyn Error>> {
+        let (filter, limit) = history::parse_search(args)?;
+
+        self.config_manager.check_and_reload().ok();
+        let entries = history::load(&self.config_manager.get_config())?;
+        Self::print_history(&entries, &filter, limit);
+
+        Ok(())
+    }
+
+    /// Print history search results, newest first
+    /// limit: maximum number of entries to show (0 = all)
+    pub fn print_history(entries: &[HistoryEntry], filter: &HistoryFilter, limit: usize) {
+        if entries.is_empty() {
+            println!("Translation history is empty");
+            println!("Set SaveTranslationHistory = true in [History] section to record translations");
+            println!();
+            return;
+        }
+
+        let results = history::search(entries, filter);
+        if results.is_empty() {
+            println!("No matching history entries");
+            println!();
+            return;
+        }
+
+        let shown = if limit == 0 {
+            results.len()
+        } else {
+            limit.min(results.len())
+        };
+
+        for (number, entry) in results.iter().take(shown) {
+            println!("{}", history::format_summary(*number, entry));
+        }
+
+        if shown < results.len() {
+            println!(
+                "Showing {} of {} matching entries (use --limit 0 to show all)",
+                shown,
+                results.len()
+            );
+        }
+        println!();
+    }
+
+    /// Export vocabulary list collected from dictionary lookups
+    /// args: [output file] [--format csv|tsv|md]
+    /// Without output file the list is printed to stdout
+    pub fn export_vocabulary(&self, args: &[String]) -> Result<(), Box<dyn Error>> {
+        let mut output_path: Option<&str> = None;
+        let mut format: Option<ExportFormat> = None;
+
+        let mut i = 0;
+        while i < args.len() {
+            match args[i].as_str() {
+                "--format" => {
+                    let value = args.get(i + 1).ok_or("Missing value for --format")?;
+                    format = Some(ExportFormat::parse(value)?);
+                    i += 2;
+                }
+                path if output_path.is_none() => {
+                    output_path = Some(path);
+                    i += 1;
+                }
+                other => return Err(format!("Unexpected argument: {}", other).into()),
+            }
+        }
+
+        // Format: --format, then file extension, then TSV
+        let format = format
+            .or_else(|| output_path.and_then(|path| ExportFormat::from_path(Path::new(path))))
+            .unwrap_or(ExportFormat::Tsv);
+
+        let vocabulary = Vocabulary::load(&ConfigManager::get_vocabulary_path(
+            &self.config_manager.get_config(),
+        ))?;
+
+        if vocabulary.words.is_empty() {
+            eprintln!("Vocabulary list is empty");
+            eprintln!("Words are added automatically when a single word is looked up in the dictionary");
             return Ok(());
         }
 
-        let command = &args[1];
+        let content = vocabulary.export(format);
+
+        match output_path {
+            Some(path) => {
+                fs::write(path, content)?;
+                println!("Exported {} words to {}", vocabulary.words.len(), path);
+            }
+            None => print!("{}", content),
+        }
+
+        Ok(())
+    }
+
+    /// Process CLI arguments and determine action
+    pub async fn process_args(&self, cli_args: CliArgs) -> Result<(), Box<dyn Error>> {
+        let CliArgs {
+            command,
+            args,
+            output_format,
+            document_mode,
+            input_file,
+            output_file,
+            bilingual,
+            resource_file,
+            read_stdin,
+            ..
+        } = cli_args;
+
+        let command = match command {
+            Some(command) => command,
+            None => {
+                if let Some(path) = resource_file {
+                    if !args.is_empty() {
+                        return Err("--i18n cannot be combined with text arguments".into());
+                    }
+                    if output_format != OutputFormat::Text {
+                        return Err("--json and --format cannot be used with --i18n".into());
+                    }
+                    return self
+                        .translate_resources(&path, output_file.as_deref())
+                        .await;
+                }
+
+                if input_file.is_some() && !args.is_empty() {
+                    return Err("--file cannot be combined with text arguments".into());
+                }
+
+                // "-", --stdin, or no text and piped input: read text from stdin
+                let read_stdin =
+                    read_stdin || (args.is_empty() && input_file.is_none() && stdin_is_piped());
+
+                if input_file.is_some() || read_stdin {
+                    return self
+                        .translate_document(
+                            input_file.as_deref(),
+                            output_file.as_deref(),
+                            document_mode,
+                            bilingual,
+                            output_format,
+                        )
+                        .await;
+                }
+
+                if output_file.is_some() {
+                    return Err("--out requires --file or text from stdin".into());
+                }
+
+                // Treat as text to translate
+                return self.translate_args(&args, output_format).await;
+            }
+        };
 
         match command.as_str() {
             "-h" | "--help" => {
@@ -98,25 +471,400 @@ impl CliHandler {
             }
             "-s" | "--speech" => {
                 // Speak the following text
-                if args.len() < 3 {
+                if args.is_empty() {
                     eprintln!("Error: No text provided for speech");
                     eprintln!("Usage: tagent -s \"text to speak\"");
                     return Ok(());
                 }
-                let text_to_speak = args[2..].join(" ");
+                let text_to_speak = args.join(" ");
                 self.speak_text(&text_to_speak).await
             }
+            "-l" | "--languages" => self.show_languages().await,
+            "--export-vocab" => {
+                if let Err(e) = self.export_vocabulary(&args) {
+                    eprintln!("Vocabulary export error: {}", e);
+                    return Err(e);
+                }
+                Ok(())
+            }
+            "--history" => {
+                if let Err(e) = self.show_history(&args) {
+                    eprintln!("History error: {}", e);
+                    return Err(e);
+                }
+                Ok(())
+            }
             "-q" => {
                 // Exit command for CLI mode (though it doesn't make much sense here)
                 println!("Exiting...");
                 Ok(())
             }
-            _ => {
-                // Treat as text to translate
-                let text_to_translate = args[1..].join(" ");
-                self.translate_text(&text_to_translate).await
+            _ => Err(format!("Unknown command: {}", command).into()),
+        }
+    }
+
+    /// Translate words left after option parsing
+    async fn translate_args(
+        &self,
+        args: &[String],
+        output_format: OutputFormat,
+    ) -> Result<(), Box<dyn Error>> {
+        if args.is_empty() {
+            if output_format == OutputFormat::Json {
+                println!("{}", output::error_json("No text provided"));
+                return Err("No text provided".into());
+            }
+            println!("Error: No text provided");
+            println!("Use --help for usage information");
+            return Ok(());
+        }
+
+        let text_to_translate = args.join(" ");
+        match output_format {
+            OutputFormat::Text => self.translate_text(&text_to_translate).await,
+            format => {
+                let result = self.translate_structured(&text_to_translate, format).await;
+                if let (Err(e), OutputFormat::Json) = (&result, format) {
+                    println!("{}", output::error_json(&e.to_string()));
+                }
+                result
+            }
+        }
+    }
+
+    /// Translate text from --file or stdin, keeping line breaks and blank lines
+    /// input: file path (None = stdin), output_path: --out file (None = stdout)
+    /// bilingual: keep original subtitle lines above translated ones
+    async fn translate_document(
+        &self,
+        input: Option<&str>,
+        output_path: Option<&str>,
+        mode: DocumentMode,
+        bilingual: bool,
+        output_format: OutputFormat,
+    ) -> Result<(), Box<dyn Error>> {
+        let result = self
+            .translate_document_inner(input, output_path, mode, bilingual, output_format)
+            .await;
+
+        if let Err(e) = &result {
+            match output_format {
+                OutputFormat::Json => println!("{}", output::error_json(&e.to_string())),
+                _ => eprintln!("Translation failed: {}", e),
+            }
+        }
+
+        result
+    }
+
+    async fn translate_document_inner(
+        &self,
+        input: Option<&str>,
+        output_path: Option<&str>,
+        mode: DocumentMode,
+        bilingual: bool,
+        output_format: OutputFormat,
+    ) -> Result<(), Box<dyn Error>> {
+        let content = match input {
+            Some(path) => fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {}", path, e))?,
+            None => {
+                let mut content = String::new();
+                io::stdin()
+                    .read_to_string(&mut content)
+                    .map_err(|e| format!("Failed to read stdin: {}", e))?;
+                content
+            }
+        };
+        // Byte order mark is not translated but kept in text output
+        let bom = if content.starts_with('\u{feff}') {
+            "\u{feff}"
+        } else {
+            ""
+        };
+        let content = content.trim_start_matches('\u{feff}');
+
+        self.config_manager.check_and_reload().ok(); // Ignore errors, use defaults
+        let config = self.config_manager.get_config();
+
+        let segments = document::split(content, mode);
+        let segments = document::limit_length(segments, config.max_chunk_length);
+
+        let texts: Vec<String> = segments
+            .iter()
+            .filter_map(|segment| match segment {
+                Segment::Text(text) => Some(text.clone()),
+                Segment::Verbatim(_) => None,
+            })
+            .collect();
+
+        if texts.is_empty() {
+            return Err(match input {
+                Some(path) => format!("No text to translate in {}", path).into(),
+                None => "No text provided on stdin".into(),
+            });
+        }
+
+        // With a language pair the whole document goes in one direction
+        let (source_code, target_codes) = self.translator.target_codes_for(&texts.join("\n")).await;
+        let target_code = single_target(target_codes)?;
+
+        if let Some(path) = input {
+            eprintln!("Translating {} ({} chunks)...", path, texts.len());
+        }
+
+        let translations = self
+            .translator
+            .translate_batch(
+                texts.clone(),
+                &source_code,
+                &target_code,
+                config.max_concurrent_requests,
+            )
+            .await?;
+
+        let provider = translations[0].provider.clone();
+        let detected_source_lang = translations[0].detected_source_lang.clone();
+        let warnings: Vec<String> = translations
+            .iter()
+            .flat_map(|translation| translation.warnings.clone())
+            .collect();
+        let mut glossary_hits = Vec::new();
+        for hit in translations.iter().flat_map(|t| &t.glossary_hits) {
+            if !glossary_hits.contains(hit) {
+                glossary_hits.push(hit.clone());
+            }
+        }
+        let mut translated_texts: Vec<String> = translations.into_iter().map(|t| t.text).collect();
+
+        if bilingual {
+            let line_ending = document::line_ending(content);
+            translated_texts = texts
+                .iter()
+                .zip(&translated_texts)
+                .map(|(original, translation)| {
+                    subtitles::bilingual(original, translation, line_ending)
+                })
+                .collect();
+        }
+
+        let translated = document::join(&segments, &translated_texts);
+
+        let rendered = match output_format {
+            OutputFormat::Text => format!("{}{}", bom, translated),
+            format => {
+                let result = TranslationOutput {
+                    source_text: content.to_string(),
+                    source_lang: source_code.clone(),
+                    detected_source_lang,
+                    target_lang: target_code.clone(),
+                    provider: provider.clone(),
+                    translation: translated.clone(),
+                    dictionary: None,
+                    warnings: warnings.clone(),
+                    glossary: glossary_hits,
+                };
+
+                match format {
+                    OutputFormat::Tsv => result.to_tsv() + "\n",
+                    _ => result.to_json() + "\n",
+                }
+            }
+        };
+
+        match output_path {
+            Some(path) => {
+                fs::write(path, &rendered)
+                    .map_err(|e| format!("Failed to write {}: {}", path, e))?;
+                eprintln!("Translation saved to {}", path);
+            }
+            None => {
+                print!("{}", rendered);
+                io::stdout().flush()?;
+            }
+        }
+
+        if let Some(note) = self.translator.fallback_note(&provider) {
+            eprintln!("{}", note);
+        }
+        if output_format == OutputFormat::Text {
+            for warning in &warnings {
+                eprintln!("Warning: {}", warning);
             }
         }
+
+        // Whole files are not copied to the clipboard or saved to history
+        if input.is_some() {
+            return Ok(());
+        }
+
+        if config.copy_to_clipboard {
+            self.copy_to_clipboard(&translated).ok(); // Ignore clipboard errors
+        }
+
+        let entry = HistoryEntry::new(
+            HistoryMode::Cli,
+            &provider,
+            &source_code,
+            &target_code,
+            content.trim(),
+            translated.trim(),
+        );
+        if let Err(e) = history::save(&config, &entry) {
+            eprintln!("History save error: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Translate untranslated entries of a gettext .po or JSON locale file
+    /// output_path: --out file (None = stdout); an existing JSON target keeps its translations
+    async fn translate_resources(
+        &self,
+        path: &str,
+        output_path: Option<&str>,
+    ) -> Result<(), Box<dyn Error>> {
+        let result = self.translate_resources_inner(path, output_path).await;
+        if let Err(e) = &result {
+            eprintln!("Translation failed: {}", e);
+        }
+        result
+    }
+
+    async fn translate_resources_inner(
+        &self,
+        path: &str,
+        output_path: Option<&str>,
+    ) -> Result<(), Box<dyn Error>> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        let bom = if content.starts_with('\u{feff}') {
+            "\u{feff}"
+        } else {
+            ""
+        };
+        let content = content.trim_start_matches('\u{feff}');
+
+        let existing = output_path
+            .filter(|output| Path::new(output).is_file())
+            .map(|output| {
+                fs::read_to_string(output).map_err(|e| format!("Failed to read {}: {}", output, e))
+            })
+            .transpose()?;
+        let existing = existing
+            .as_deref()
+            .map(|e| e.trim_start_matches('\u{feff}'));
+
+        let resource = Resource::parse(Path::new(path), content, existing)
+            .map_err(|e| format!("{}: {}", path, e))?;
+        let texts = resource.untranslated();
+
+        let translations = if texts.is_empty() {
+            eprintln!(
+                "Nothing to translate: all entries in {} are translated",
+                path
+            );
+            HashMap::new()
+        } else {
+            eprintln!("Translating {} ({} entries)...", path, texts.len());
+            self.translate_entries(&texts).await?
+        };
+
+        let rendered = resource.render(&translations)?;
+        let output = format!("{}{}", bom, rendered.content);
+
+        for warning in &rendered.warnings {
+            eprintln!("Warning: {}", warning);
+        }
+
+        match output_path {
+            Some(output_path) => {
+                fs::write(output_path, &output)
+                    .map_err(|e| format!("Failed to write {}: {}", output_path, e))?;
+                eprintln!("Translation saved to {}", output_path);
+            }
+            None => {
+                print!("{}", output);
+                io::stdout().flush()?;
+            }
+        }
+
+        if rendered.translated.is_empty() {
+            return Ok(());
+        }
+
+        if resource.supports_fuzzy() {
+            eprintln!(
+                "{} entries machine-translated and marked fuzzy",
+                rendered.translated.len()
+            );
+        } else {
+            // JSON has no fuzzy flag, list the keys for review instead
+            eprintln!(
+                "{} keys machine-translated (fuzzy, review before release):",
+                rendered.translated.len()
+            );
+            for key in &rendered.translated {
+                eprintln!("  {}", key);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Translate resource texts; text -> translation
+    /// Entries whose placeholders were lost are skipped with a warning
+    async fn translate_entries(
+        &self,
+        texts: &[String],
+    ) -> Result<HashMap<String, String>, Box<dyn Error>> {
+        self.config_manager.check_and_reload().ok(); // Ignore errors, use defaults
+        let config = self.config_manager.get_config();
+        let (source_code, target_codes) = self.translator.target_codes_for(&texts.join("\n")).await;
+        let target_code = single_target(target_codes)?;
+
+        // Surrounding whitespace is kept, texts without letters are not sent
+        let pending: Vec<&String> = texts
+            .iter()
+            .filter(|text| protect::protect(text).text.chars().any(char::is_alphabetic))
+            .collect();
+
+        let results = self
+            .translator
+            .translate_batch(
+                pending.iter().map(|text| text.trim().to_string()).collect(),
+                &source_code,
+                &target_code,
+                config.max_concurrent_requests,
+            )
+            .await?;
+
+        if let Some(note) = results
+            .first()
+            .and_then(|result| self.translator.fallback_note(&result.provider))
+        {
+            eprintln!("{}", note);
+        }
+
+        let mut translations = HashMap::new();
+        for (text, result) in pending.iter().zip(results) {
+            if let Some(warning) = result.warnings.first() {
+                eprintln!("Skipped \"{}\": {}", text.trim(), warning);
+                continue;
+            }
+
+            let core = text.trim();
+            let start = text.len() - text.trim_start().len();
+            let translation = format!(
+                "{}{}{}",
+                &text[..start],
+                result.text.trim(),
+                &text[start + core.len()..]
+            );
+            translations.insert(text.to_string(), translation);
+        }
+
+        Ok(translations)
     }
 
     /// Main translation function for CLI
@@ -130,7 +878,15 @@ impl CliHandler {
         // Load current configuration
         self.config_manager.check_and_reload().ok(); // Ignore errors, use defaults
         let config = self.config_manager.get_config();
-        let (source_code, target_code) = self.config_manager.get_language_codes();
+        let (source_code, target_codes) = self.translator.target_codes_for(text).await;
+        let target_code = target_codes.first().cloned().unwrap_or_default();
+
+        // Several target languages: one block per language, no dictionary
+        if target_codes.len() > 1 {
+            return self
+                .perform_multi_translation(text, &source_code, &target_codes, &config)
+                .await;
+        }
 
         // Check if it's a single word and dictionary feature is enabled
         if config.show_dictionary && self.is_single_word(text) {
@@ -139,9 +895,15 @@ impl CliHandler {
                 .get_dictionary_entry_public(text, &source_code, &target_code)
                 .await
             {
-                Ok(dictionary_info) => {
+                Ok(dictionary) => {
+                    let dictionary_info = dictionary.text;
                     println!("{}", dictionary_info);
 
+                    // Keep stdout clean for piping, report fallback on stderr
+                    if let Some(note) = self.translator.fallback_note(&dictionary.provider) {
+                        eprintln!("{}", note);
+                    }
+
                     if config.copy_to_clipboard {
                         if let Err(e) = self.copy_to_clipboard(&dictionary_info) {
                             println!("Clipboard error: {}", e);
@@ -149,13 +911,15 @@ impl CliHandler {
                     }
 
                     // Сохраняем словарную статью в историю
-                    if let Err(e) = self.save_translation_history(
-                        text,
-                        &dictionary_info,
+                    let entry = HistoryEntry::new(
+                        HistoryMode::Cli,
+                        &dictionary.provider,
                         &source_code,
                         &target_code,
-                        &config,
-                    ) {
+                        text,
+                        &dictionary_info,
+                    );
+                    if let Err(e) = history::save(&config, &entry) {
                         println!("History save error: {}", e);
                     }
 
@@ -173,6 +937,179 @@ impl CliHandler {
             .await
     }
 
+    /// Translate text and print result as JSON or TSV
+    /// Single words also include the structured dictionary entry
+    /// Several target languages give a JSON array or one TSV line per language
+    async fn translate_structured(
+        &self,
+        text: &str,
+        format: OutputFormat,
+    ) -> Result<(), Box<dyn Error>> {
+        if text.trim().is_empty() {
+            return Err("Empty text provided".into());
+        }
+
+        self.config_manager.check_and_reload().ok(); // Ignore errors, use defaults
+        let config = self.config_manager.get_config();
+        let (source_code, target_codes) = self.translator.target_codes_for(text).await;
+
+        let results = self
+            .translator
+            .translate_to_targets(text, &source_code, &target_codes)
+            .await;
+
+        let mut outputs = Vec::with_capacity(results.len());
+        let mut clipboard_blocks = Vec::new();
+
+        for (target_code, result) in results {
+            let translation = result?;
+
+            // Dictionary is optional: lookup errors leave it empty
+            let dictionary = if config.show_dictionary && self.is_single_word(text) {
+                self.translator
+                    .lookup_dictionary_entry(text, &source_code, &target_code)
+                    .await
+                    .ok()
+                    .flatten()
+            } else {
+                None
+            };
+
+            let selected = |setting: &str| {
+                ConfigManager::is_target_selected(setting, &target_codes, &target_code)
+            };
+
+            if selected(&config.clipboard_targets) {
+                let language = ConfigManager::language_name(&config, &target_code);
+                clipboard_blocks.push((format!("[{}]: ", language), translation.text.clone()));
+            }
+
+            if selected(&config.history_targets) {
+                let entry = HistoryEntry::new(
+                    HistoryMode::Cli,
+                    &translation.provider,
+                    &source_code,
+                    &target_code,
+                    text,
+                    &translation.text,
+                );
+                if let Err(e) = history::save(&config, &entry) {
+                    eprintln!("History save error: {}", e);
+                }
+            }
+
+            outputs.push(TranslationOutput {
+                source_text: text.to_string(),
+                source_lang: source_code.clone(),
+                detected_source_lang: translation.detected_source_lang,
+                target_lang: target_code,
+                provider: translation.provider,
+                translation: translation.text,
+                dictionary,
+                warnings: translation.warnings,
+                glossary: translation.glossary_hits,
+            });
+        }
+
+        match (format, outputs.as_slice()) {
+            (OutputFormat::Tsv, outputs) => {
+                for output in outputs {
+                    println!("{}", output.to_tsv());
+                }
+            }
+            (_, [output]) => println!("{}", output.to_json()),
+            (_, outputs) => println!("{}", output::list_to_json(outputs)),
+        }
+
+        if config.copy_to_clipboard {
+            if let Some(clipboard_text) = translator::labeled_clipboard_text(&clipboard_blocks) {
+                self.copy_to_clipboard(&clipboard_text).ok(); // Ignore clipboard errors
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Translate into several target languages and print one block per language
+    /// Labels are colored on a terminal; fallback notes and warnings go to stderr
+    async fn perform_multi_translation(
+        &self,
+        text: &str,
+        source_code: &str,
+        target_codes: &[String],
+        config: &crate::config::Config,
+    ) -> Result<(), Box<dyn Error>> {
+        let results = self
+            .translator
+            .translate_to_targets(text, source_code, target_codes)
+            .await;
+
+        let mut clipboard_blocks = Vec::new();
+        let mut errors = Vec::new();
+
+        for (target_code, result) in &results {
+            let label = format!("[{}]: ", ConfigManager::language_name(config, target_code));
+
+            let translation = match result {
+                Ok(translation) => translation,
+                Err(e) => {
+                    eprintln!("{}Translation failed: {}", label, e);
+                    errors.push(e.clone());
+                    continue;
+                }
+            };
+
+            if let Some(color) = ConfigManager::parse_color(&config.target_prompt_color) {
+                print!("{}", label.color(color));
+            } else {
+                print!("{}", label);
+            }
+            println!("{}", translation.text);
+
+            if let Some(note) = self.translator.fallback_note(&translation.provider) {
+                eprintln!("{}", note);
+            }
+            for warning in &translation.warnings {
+                eprintln!("Warning: {}", warning);
+            }
+
+            let selected = |setting: &str| {
+                ConfigManager::is_target_selected(setting, target_codes, target_code)
+            };
+
+            if selected(&config.clipboard_targets) {
+                clipboard_blocks.push((label.clone(), translation.text.clone()));
+            }
+
+            if selected(&config.history_targets) {
+                let entry = HistoryEntry::new(
+                    HistoryMode::Cli,
+                    &translation.provider,
+                    source_code,
+                    target_code,
+                    text,
+                    &translation.text,
+                );
+                if let Err(e) = history::save(config, &entry) {
+                    println!("History save error: {}", e);
+                }
+            }
+        }
+
+        if config.copy_to_clipboard {
+            if let Some(clipboard_text) = translator::labeled_clipboard_text(&clipboard_blocks) {
+                self.copy_to_clipboard(&clipboard_text).ok(); // Ignore clipboard errors
+            }
+        }
+
+        // Fail only if no language could be translated
+        if errors.len() == results.len() {
+            return Err(errors.join("; ").into());
+        }
+
+        Ok(())
+    }
+
     /// Perform translation and display results
     async fn perform_translation(
         &self,
@@ -187,21 +1124,31 @@ impl CliHandler {
             .translate_text_public(text, source_code, target_code)
             .await
         {
-            Ok(translated_text) => {
+            Ok(translation) => {
+                let translated_text = translation.text;
                 println!("{}", translated_text);
 
+                if let Some(note) = self.translator.fallback_note(&translation.provider) {
+                    eprintln!("{}", note);
+                }
+                for warning in &translation.warnings {
+                    eprintln!("Warning: {}", warning);
+                }
+
                 if config.copy_to_clipboard {
                     self.copy_to_clipboard(&translated_text).ok(); // Ignore clipboard errors
                 }
 
                 // Сохраняем перевод в историю
-                if let Err(e) = self.save_translation_history(
-                    text,
-                    &translated_text,
+                let entry = HistoryEntry::new(
+                    HistoryMode::Cli,
+                    &translation.provider,
                     source_code,
                     target_code,
-                    config,
-                ) {
+                    text,
+                    &translated_text,
+                );
+                if let Err(e) = history::save(config, &entry) {
                     println!("History save error: {}", e);
                 }
             }
@@ -246,13 +1193,16 @@ impl CliHandler {
 
         // Load current configuration to get source language
         self.config_manager.check_and_reload().ok();
-        let (source_code, _) = self.config_manager.get_language_codes();
+        let (source_code, _) = self.translator.language_codes_for(text).await;
 
-        // If source language is "auto", use English by default for TTS
+        // If source language is "auto", detect it; English if detection fails
         let speech_lang = if source_code == "auto" {
-            "en"
+            self.translator
+                .detect_language(text)
+                .await
+                .unwrap_or_else(|| "en".to_string())
         } else {
-            &source_code
+            source_code
         };
 
         // Create stop flag for cancellation
@@ -301,3 +1251,134 @@ impl CliHandler {
         }
     }
 }
+
+/// Stdin is redirected from a pipe or a file
+/// A missing or invalid handle (Task Scheduler, shortcuts, services) or NUL is not input
+pub fn stdin_is_piped() -> bool {
+    if io::stdin().is_terminal() {
+        return false;
+    }
+
+    unsafe {
+        match GetStdHandle(STD_INPUT_HANDLE) {
+            Ok(handle) if !handle.is_invalid() => {
+                matches!(GetFileType(handle), FILE_TYPE_PIPE | FILE_TYPE_DISK)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Files are translated into one language at a time
+fn single_target(target_codes: Vec<String>) -> Result<String, Box<dyn Error>> {
+    match target_codes.as_slice() {
+        [target_code] => Ok(target_code.clone()),
+        _ => Err(format!(
+            "Files are translated into one language at a time, got {}; use --to with a single language",
+            target_codes.join(", ")
+        )
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> CliArgs {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        CliArgs::parse(&args).unwrap()
+    }
+
+    #[test]
+    fn test_parse_translation_options() {
+        let parsed = parse(&["--from", "en", "good", "--to=de", "morning", "--no-dict"]);
+        assert_eq!(parsed.command, None);
+        assert_eq!(parsed.args, vec!["good", "morning"]);
+        assert_eq!(parsed.overrides.source_language.as_deref(), Some("en"));
+        assert_eq!(parsed.overrides.target_language.as_deref(), Some("de"));
+        assert_eq!(parsed.overrides.show_dictionary, Some(false));
+        assert_eq!(parsed.overrides.copy_to_clipboard, None);
+
+        let parsed = parse(&["--provider", "deepl, google", "--json", "cat"]);
+        assert_eq!(
+            parsed.overrides.translate_provider.as_deref(),
+            Some("deepl, google")
+        );
+        assert_eq!(parsed.output_format, OutputFormat::Json);
+    }
+
+    #[test]
+    fn test_parse_commands_and_literal_text() {
+        let parsed = parse(&["--to", "ru", "-h"]);
+        assert_eq!(parsed.command.as_deref(), Some("-h"));
+
+        // Command arguments are passed through unchanged
+        let parsed = parse(&["--export-vocab", "--format", "md"]);
+        assert_eq!(parsed.command.as_deref(), Some("--export-vocab"));
+        assert_eq!(parsed.args, vec!["--format", "md"]);
+        assert_eq!(parsed.output_format, OutputFormat::Text);
+
+        let parsed = parse(&["--to", "ru", "--", "-h", "--no-dict"]);
+        assert_eq!(parsed.command, None);
+        assert_eq!(parsed.args, vec!["-h", "--no-dict"]);
+        assert_eq!(parsed.overrides.show_dictionary, None);
+
+        assert_eq!(parse(&["-5", "degrees"]).args, vec!["-5", "degrees"]);
+
+        let parsed = parse(&["--lines", "-"]);
+        assert_eq!(parsed.document_mode, DocumentMode::Lines);
+        assert!(parsed.args.is_empty());
+        assert!(parsed.read_stdin);
+        assert!(parse(&["--stdin"]).read_stdin);
+        assert_eq!(parse(&["-", "-"]).args, vec!["-", "-"]);
+
+        let parsed = parse(&["--file", "in.txt", "--out=out.txt"]);
+        assert_eq!(parsed.input_file.as_deref(), Some("in.txt"));
+        assert_eq!(parsed.output_file.as_deref(), Some("out.txt"));
+        assert!(parsed.args.is_empty());
+
+        let parsed = parse(&["--markdown", "README.md", "--to", "de"]);
+        assert_eq!(parsed.input_file.as_deref(), Some("README.md"));
+        assert_eq!(parsed.document_mode, DocumentMode::Markdown);
+
+        let parsed = parse(&["--subtitles", "movie.srt", "--bilingual"]);
+        assert_eq!(parsed.document_mode, DocumentMode::Subtitles);
+        assert!(parsed.bilingual);
+
+        let parsed = parse(&["--i18n", "en.json", "--out", "ru.json"]);
+        assert_eq!(parsed.resource_file.as_deref(), Some("en.json"));
+        assert_eq!(parsed.input_file, None);
+        assert_eq!(parse(&["--config"]).command.as_deref(), Some("--config"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parse_err = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+            CliArgs::parse(&args).unwrap_err().to_string()
+        };
+
+        assert!(parse_err(&["--to"]).contains("Missing value for --to"));
+        assert!(parse_err(&["hello", "-x"]).contains("Unknown option: -x"));
+        assert!(parse_err(&["--format=xml", "cat"]).contains("Unknown output format"));
+        assert!(parse_err(&["--config", "missing.conf", "cat"]).contains("not found"));
+        assert!(parse_err(&["--file", "a.txt", "--bilingual"]).contains("--subtitles"));
+        assert!(parse_err(&["--i18n", "ru.po", "--file", "a.txt"]).contains("--i18n"));
+        assert!(parse_err(&["--stdin", "hello"]).contains("--stdin cannot be combined"));
+    }
+
+    #[test]
+    fn test_requested_format_of_invalid_arguments() {
+        let format = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+            CliArgs::requested_format(&args)
+        };
+
+        assert_eq!(format(&["--json", "-x"]), OutputFormat::Json);
+        assert_eq!(format(&["-x", "--format", "json"]), OutputFormat::Json);
+        assert_eq!(format(&["--format=tsv", "--to"]), OutputFormat::Tsv);
+        assert_eq!(format(&["--format=xml", "cat"]), OutputFormat::Text);
+        assert_eq!(format(&["--", "--json"]), OutputFormat::Text);
+    }
+}
@@ -0,0 +1,470 @@
+// Offline language detection by writing system, frequent words and letter combinations
+// Used for source language checks and speech when no provider answer is available
+
+use crate::languages::{self, Script};
+
+/// Language written in a shared script
+struct Profile {
+    code: &'static str,
+    script: Script,
+    words: &'static [&'static str],  // Most frequent words
+    letters: &'static str,           // Letters other languages of the script rarely use
+    ngrams: &'static [&'static str], // Typical letter combinations
+}
+
+/// First profile of a script is the default when there is no other evidence
+const PROFILES: &[Profile] = &[
+    Profile {
+        code: "en",
+        script: Script::Latin,
+        words: &[
+            "the", "and", "is", "are", "of", "to", "in", "that", "it", "you", "for", "with",
+            "this", "was", "have", "be", "not", "on", "what", "how", "my", "your", "we", "they",
+            "do", "does", "can", "will", "hello",
+        ],
+        letters: "",
+        ngrams: &["th", "ing", "ght", "wh"],
+    },
+    Profile {
+        code: "de",
+        script: Script::Latin,
+        words: &[
+            "der", "die", "das", "und", "ist", "nicht", "ich", "du", "sie", "es", "ein", "eine",
+            "mit", "zu", "auf", "den", "dem", "von", "für", "wie", "was", "sind", "auch", "wir",
+            "haben", "bitte", "hallo",
+        ],
+        letters: "äöüß",
+        ngrams: &["sch", "ich", "cht", "ung"],
+    },
+    Profile {
+        code: "fr",
+        script: Script::Latin,
+        words: &[
+            "le", "la", "les", "et", "est", "un", "une", "des", "du", "je", "tu", "il", "elle",
+            "nous", "vous", "pas", "ne", "que", "qui", "dans", "pour", "avec", "ce", "sur", "au",
+            "bonjour",
+        ],
+        letters: "éèêàçùâîôœë",
+        ngrams: &["eau", "oi", "ou", "qu"],
+    },
+    Profile {
+        code: "es",
+        script: Script::Latin,
+        words: &[
+            "el", "la", "los", "las", "y", "es", "un", "una", "de", "que", "en", "no", "por",
+            "para", "con", "se", "lo", "como", "está", "muy", "pero", "hola", "yo", "qué",
+        ],
+        letters: "ñ¿¡áíóú",
+        ngrams: &["ción", "dad", "ll"],
+    },
+    Profile {
+        code: "it",
+        script: Script::Latin,
+        words: &[
+            "il", "lo", "la", "gli", "le", "e", "è", "di", "che", "non", "un", "una", "per", "con",
+            "sono", "mi", "ti", "ciao", "come", "questo", "del", "della", "anche", "ma",
+        ],
+        letters: "ìò",
+        ngrams: &["zione", "gli", "cch", "tt"],
+    },
+    Profile {
+        code: "pt",
+        script: Script::Latin,
+        words: &[
+            "o", "a", "os", "as", "e", "é", "um", "uma", "de", "que", "não", "em", "por", "para",
+            "com", "se", "do", "da", "você", "eu", "obrigado", "muito", "isso", "olá",
+        ],
+        letters: "ãõ",
+        ngrams: &["ção", "nh", "lh"],
+    },
+    Profile {
+        code: "nl",
+        script: Script::Latin,
+        words: &[
+            "de", "het", "een", "en", "is", "van", "ik", "je", "niet", "dat", "die", "met", "voor",
+            "op", "zijn", "wat", "er", "maar", "ook", "hoe", "dit", "wij", "hallo",
+        ],
+        letters: "",
+        ngrams: &["ij", "oe", "aa", "ee"],
+    },
+    Profile {
+        code: "pl",
+        script: Script::Latin,
+        words: &[
+            "i", "w", "z", "na", "nie", "się", "to", "jest", "że", "do", "co", "jak", "ale", "tak",
+            "ja", "ty", "po", "od", "czy", "dla", "cześć",
+        ],
+        letters: "ąęłśźżńć",
+        ngrams: &["cz", "sz", "rz", "ie"],
+    },
+    Profile {
+        code: "tr",
+        script: Script::Latin,
+        words: &[
+            "ve", "bir", "bu", "da", "de", "ne", "için", "ile", "ben", "sen", "değil", "çok",
+            "var", "yok", "mi", "ama", "gibi", "nasıl", "merhaba",
+        ],
+        letters: "ğşıç",
+        ngrams: &["ler", "lar"],
+    },
+    Profile {
+        code: "ru",
+        script: Script::Cyrillic,
+        words: &[
+            "и",
+            "в",
+            "не",
+            "на",
+            "я",
+            "что",
+            "он",
+            "с",
+            "это",
+            "как",
+            "а",
+            "по",
+            "но",
+            "они",
+            "мы",
+            "вы",
+            "ты",
+            "да",
+            "нет",
+            "все",
+            "так",
+            "его",
+            "к",
+            "у",
+            "же",
+            "привет",
+        ],
+        letters: "ыэё",
+        ngrams: &["ого", "ение", "ться"],
+    },
+    Profile {
+        code: "uk",
+        script: Script::Cyrillic,
+        words: &[
+            "і",
+            "в",
+            "не",
+            "на",
+            "я",
+            "що",
+            "це",
+            "як",
+            "та",
+            "з",
+            "до",
+            "але",
+            "ми",
+            "ви",
+            "ти",
+            "так",
+            "ні",
+            "він",
+            "вона",
+            "є",
+            "привіт",
+        ],
+        letters: "іїєґ",
+        ngrams: &["ння", "ськ"],
+    },
+    Profile {
+        code: "bg",
+        script: Script::Cyrillic,
+        words: &[
+            "и",
+            "в",
+            "не",
+            "на",
+            "аз",
+            "че",
+            "това",
+            "как",
+            "е",
+            "с",
+            "да",
+            "за",
+            "от",
+            "са",
+            "ще",
+            "но",
+            "ти",
+            "той",
+            "тя",
+            "ние",
+            "много",
+            "здравей",
+        ],
+        letters: "",
+        ngrams: &["ът", "ия"],
+    },
+    Profile {
+        code: "ar",
+        script: Script::Arabic,
+        words: &["في", "من", "على", "إلى", "هذا", "أن", "ما", "لا", "مرحبا"],
+        letters: "ةإأ",
+        ngrams: &["ال"],
+    },
+    Profile {
+        code: "fa",
+        script: Script::Arabic,
+        words: &["و", "در", "به", "از", "که", "این", "است", "را", "سلام"],
+        letters: "پچژگکی",
+        ngrams: &[],
+    },
+    Profile {
+        code: "ur",
+        script: Script::Arabic,
+        words: &["کے", "میں", "ہے", "کی", "اور", "سے"],
+        letters: "ٹڈڑںےھ",
+        ngrams: &[],
+    },
+];
+
+/// Detect language of text; None if it has no letters
+pub fn detect(text: &str) -> Option<&'static str> {
+    let script = dominant_script(text)?;
+
+    if let Some(code) = single_language(script) {
+        return Some(code);
+    }
+
+    let scores = scores(text, script);
+    let best = scores
+        .iter()
+        .fold(None, |best: Option<&(&str, usize)>, score| match best {
+            Some(best) if best.1 >= score.1 => Some(best),
+            _ => Some(score),
+        })?;
+
+    Some(best.0)
+}
+
+/// Whether text may be in the language (code)
+/// False only if the text is in another script or clearly matches another language
+pub fn is_language(text: &str, code: &str) -> bool {
+    let (Some(expected_script), Some(script)) = (language_script(code), dominant_script(text))
+    else {
+        return true; // Unknown language or no letters: nothing to check against
+    };
+
+    if script != expected_script {
+        // Japanese uses Chinese characters, a text without kana can be either
+        return script == Script::Han && expected_script == Script::Kana;
+    }
+
+    // No profile to compare with other languages of the script
+    if !PROFILES.iter().any(|profile| profile.code == code) {
+        return true;
+    }
+
+    // Too short to tell languages of one script apart
+    if words(text).count() < 3 {
+        return true;
+    }
+
+    let scores = scores(text, script);
+    let best = scores.iter().map(|(_, score)| *score).max().unwrap_or(0);
+    let expected = scores
+        .iter()
+        .find(|(language, _)| *language == code)
+        .map_or(0, |(_, score)| *score);
+
+    best == 0 || expected * 2 >= best
+}
+
+/// Whether the writing system alone cannot tell the two languages apart
+/// True for unknown languages, and for Chinese and Japanese (both use Chinese characters)
+pub fn same_script(first: &str, second: &str) -> bool {
+    match (language_script(first), language_script(second)) {
+        (Some(Script::Kana | Script::Han), Some(Script::Kana | Script::Han)) => true,
+        (Some(first), Some(second)) => first == second,
+        _ => true,
+    }
+}
+
+fn language_script(code: &str) -> Option<Script> {
+    languages::find(code).map(|language| language.script)
+}
+
+/// Evidence score of every language written in the script, in profile order
+fn scores(text: &str, script: Script) -> Vec<(&'static str, usize)> {
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = words(&lower).collect();
+
+    PROFILES
+        .iter()
+        .filter(|profile| profile.script == script)
+        .map(|profile| {
+            let word_hits = words
+                .iter()
+                .filter(|word| profile.words.contains(word))
+                .count();
+            let letter_hits = lower
+                .chars()
+                .filter(|c| profile.letters.contains(*c))
+                .count();
+            let ngram_hits: usize = profile
+                .ngrams
+                .iter()
+                .map(|ngram| lower.matches(ngram).count())
+                .sum();
+
+            (profile.code, word_hits * 2 + letter_hits * 3 + ngram_hits)
+        })
+        .collect()
+}
+
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+}
+
+/// Script used by most letters; Chinese characters count as Japanese when kana is present
+fn dominant_script(text: &str) -> Option<Script> {
+    let mut counts: Vec<(Script, usize)> = Vec::new();
+
+    for script in text.chars().filter_map(script_of) {
+        match counts.iter_mut().find(|(s, _)| *s == script) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((script, 1)),
+        }
+    }
+
+    let has_kana = counts.iter().any(|(script, _)| *script == Script::Kana);
+    if has_kana {
+        let han = counts
+            .iter()
+            .find(|(script, _)| *script == Script::Han)
+            .map_or(0, |(_, count)| *count);
+        counts.retain(|(script, _)| *script != Script::Han);
+        if let Some((_, count)) = counts.iter_mut().find(|(s, _)| *s == Script::Kana) {
+            *count += han;
+        }
+    }
+
+    counts
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(script, _)| script)
+}
+
+/// Main language of a script without profiles, e.g. Hindi for Devanagari
+fn single_language(script: Script) -> Option<&'static str> {
+    if PROFILES.iter().any(|profile| profile.script == script) {
+        return None;
+    }
+
+    languages::LANGUAGES
+        .iter()
+        .find(|language| language.script == script)
+        .map(|language| language.code)
+}
+
+fn script_of(c: char) -> Option<Script> {
+    if !c.is_alphabetic() {
+        return None;
+    }
+
+    let script = match c as u32 {
+        0x0041..=0x024F | 0x1E00..=0x1EFF => Script::Latin,
+        0x0370..=0x03FF | 0x1F00..=0x1FFF => Script::Greek,
+        0x0400..=0x052F => Script::Cyrillic,
+        0x0530..=0x058F => Script::Armenian,
+        0x0590..=0x05FF => Script::Hebrew,
+        0x0600..=0x06FF | 0x0750..=0x077F | 0xFB50..=0xFEFF => Script::Arabic,
+        0x0900..=0x097F => Script::Devanagari,
+        0x0980..=0x09FF => Script::Bengali,
+        0x0A00..=0x0A7F => Script::Gurmukhi,
+        0x0A80..=0x0AFF => Script::Gujarati,
+        0x0B80..=0x0BFF => Script::Tamil,
+        0x0C00..=0x0C7F => Script::Telugu,
+        0x0C80..=0x0CFF => Script::Kannada,
+        0x0D00..=0x0D7F => Script::Malayalam,
+        0x0D80..=0x0DFF => Script::Sinhala,
+        0x0E00..=0x0E7F => Script::Thai,
+        0x0E80..=0x0EFF => Script::Lao,
+        0x1000..=0x109F => Script::Myanmar,
+        0x10A0..=0x10FF => Script::Georgian,
+        0x1200..=0x139F => Script::Ethiopic,
+        0x1780..=0x17FF => Script::Khmer,
+        0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Script::Hangul,
+        0x3040..=0x30FF | 0x31F0..=0x31FF => Script::Kana,
+        0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => Script::Han,
+        _ => return None,
+    };
+
+    Some(script)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        let samples = [
+            ("The quick brown fox jumps over the lazy dog", "en"),
+            (
+                "Der schnelle braune Fuchs springt über den faulen Hund",
+                "de",
+            ),
+            ("Je ne sais pas ce que c'est", "fr"),
+            ("¿Dónde está la biblioteca?", "es"),
+            ("Questo è un libro della biblioteca", "it"),
+            ("Eu não sei o que é isso", "pt"),
+            ("Ik weet niet wat dat is", "nl"),
+            ("Nie wiem, co to jest", "pl"),
+            ("Bu çok güzel bir gün", "tr"),
+            ("Привет, как дела?", "ru"),
+            ("Привіт, як справи?", "uk"),
+            ("Καλημέρα κόσμε", "el"),
+            ("こんにちは世界", "ja"),
+            ("你好世界", "zh"),
+            ("안녕하세요", "ko"),
+            ("नमस्ते दुनिया", "hi"),
+            ("مرحبا بالعالم", "ar"),
+            ("வணக்கம் உலகம்", "ta"),
+            ("שלום עולם", "he"),
+        ];
+
+        for (text, code) in samples {
+            assert_eq!(detect(text), Some(code), "{}", text);
+        }
+
+        // Unknown words fall back to the main language of the script
+        assert_eq!(detect("Кошка"), Some("ru"));
+        assert_eq!(detect("12345 ?!"), None);
+    }
+
+    #[test]
+    fn test_is_language() {
+        assert!(is_language("Hello world", "en"));
+        assert!(!is_language("Hello world", "ru"));
+        assert!(!is_language("Привет мир", "en"));
+        assert!(is_language("Привет мир", "ru"));
+
+        // Short Latin texts are not rejected, longer ones are compared
+        assert!(is_language("Hund", "en"));
+        assert!(!is_language("Das ist nicht gut, ich weiß", "en"));
+        assert!(is_language("Das ist nicht gut, ich weiß", "de"));
+
+        // Kanji-only text can be Japanese, unknown codes are not checked
+        assert!(is_language("世界", "ja"));
+        assert!(is_language("Hello", "xx"));
+        assert!(is_language("Salamat po sa inyong lahat", "tl"));
+        assert!(is_language("42", "en"));
+    }
+
+    #[test]
+    fn test_same_script() {
+        assert!(!same_script("en", "ru"));
+        assert!(!same_script("ko", "de"));
+        assert!(same_script("en", "de"));
+        assert!(same_script("uk", "ru"));
+        assert!(same_script("ja", "zh"));
+        assert!(same_script("en", "xx"));
+    }
+}
@@ -0,0 +1,333 @@
+use crate::markdown;
+use crate::subtitles;
+
+/// Part of a document: text to translate or whitespace kept as is
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    Text(String),
+    Verbatim(String), // Indentation, line breaks and blank lines
+}
+
+/// How a document is split into segments
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocumentMode {
+    Paragraphs, // Blank-line separated paragraphs (default)
+    Lines,      // Every line separately
+    Markdown,   // Prose only, Markdown markup kept verbatim
+    Subtitles,  // SRT/WebVTT cue texts, indices and timestamps kept verbatim
+}
+
+/// Split content into segments according to the mode
+pub fn split(content: &str, mode: DocumentMode) -> Vec<Segment> {
+    match mode {
+        DocumentMode::Paragraphs => split_paragraphs(content),
+        DocumentMode::Lines => split_lines(content),
+        DocumentMode::Markdown => markdown::split(content),
+        DocumentMode::Subtitles => subtitles::split(content),
+    }
+}
+
+/// Split content into lines; indentation, line endings and blank lines stay verbatim
+pub fn split_lines(content: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+
+    for line in content.split_inclusive('\n') {
+        push_trimmed(&mut segments, line);
+    }
+
+    segments
+}
+
+/// Split content into paragraphs separated by blank lines
+/// Line breaks inside a paragraph are part of its text
+pub fn split_paragraphs(content: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut paragraph_start: Option<usize> = None;
+    let mut offset = 0;
+
+    for line in content.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(start) = paragraph_start.take() {
+                push_trimmed(&mut segments, &content[start..offset]);
+            }
+            push_verbatim(&mut segments, line);
+        } else if paragraph_start.is_none() {
+            paragraph_start = Some(offset);
+        }
+        offset += line.len();
+    }
+
+    if let Some(start) = paragraph_start {
+        push_trimmed(&mut segments, &content[start..]);
+    }
+
+    segments
+}
+
+/// Split text segments longer than max_chars on sentence boundaries,
+/// then on spaces; 0 = no limit
+pub fn limit_length(segments: Vec<Segment>, max_chars: usize) -> Vec<Segment> {
+    if max_chars == 0 {
+        return segments;
+    }
+
+    let mut result = Vec::new();
+
+    for segment in segments {
+        match segment {
+            Segment::Text(text) if text.chars().count() > max_chars => {
+                push_grouped(&mut result, &text, max_chars, SplitLevel::Sentences);
+            }
+            Segment::Verbatim(text) => push_verbatim(&mut result, &text),
+            text => result.push(text),
+        }
+    }
+
+    result
+}
+
+/// Assemble document, replacing text segments with translations in order
+pub fn join(segments: &[Segment], translations: &[String]) -> String {
+    let mut translations = translations.iter();
+    let mut result = String::new();
+
+    for segment in segments {
+        match segment {
+            Segment::Text(text) => result.push_str(translations.next().unwrap_or(text)),
+            Segment::Verbatim(text) => result.push_str(text),
+        }
+    }
+
+    result
+}
+
+#[derive(Clone, Copy)]
+enum SplitLevel {
+    Sentences,
+    Words,
+    Characters,
+}
+
+/// Split text into units of the given level and group them into chunks of at most max_chars;
+/// units that are still too long are split at the next level
+fn push_grouped(segments: &mut Vec<Segment>, text: &str, max_chars: usize, level: SplitLevel) {
+    let units: Vec<&str> = match level {
+        SplitLevel::Sentences => split_sentences(text),
+        SplitLevel::Words => text.split_inclusive(char::is_whitespace).collect(),
+        SplitLevel::Characters => split_characters(text, max_chars),
+    };
+
+    let mut chunk = String::new();
+
+    for unit in units {
+        let unit_length = unit.trim_end().chars().count();
+
+        if unit_length > max_chars {
+            push_trimmed(segments, &chunk);
+            chunk.clear();
+
+            let next_level = match level {
+                SplitLevel::Sentences => SplitLevel::Words,
+                _ => SplitLevel::Characters,
+            };
+            push_grouped(segments, unit, max_chars, next_level);
+            continue;
+        }
+
+        if !chunk.is_empty() && chunk.chars().count() + unit_length > max_chars {
+            push_trimmed(segments, &chunk);
+            chunk.clear();
+        }
+        chunk.push_str(unit);
+    }
+
+    push_trimmed(segments, &chunk);
+}
+
+/// Sentences with their trailing whitespace
+/// A sentence ends with . ! ? or … followed by whitespace, or with a CJK full stop
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((_, c)) = chars.next() {
+        let is_end = match c {
+            '。' | '！' | '？' => true,
+            '.' | '!' | '?' | '…' => {
+                !matches!(chars.peek(), Some(&(_, next)) if !next.is_whitespace())
+            }
+            _ => false,
+        };
+
+        if !is_end {
+            continue;
+        }
+
+        while let Some(&(_, next)) = chars.peek() {
+            if !next.is_whitespace() {
+                break;
+            }
+            chars.next();
+        }
+
+        let end = chars.peek().map_or(text.len(), |&(i, _)| i);
+        sentences.push(&text[start..end]);
+        start = end;
+    }
+
+    if start < text.len() {
+        sentences.push(&text[start..]);
+    }
+
+    sentences
+}
+
+/// Pieces of exactly max_chars characters (the last one may be shorter)
+fn split_characters(text: &str, max_chars: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+
+    for (count, (i, _)) in text.char_indices().enumerate() {
+        if count > 0 && count % max_chars == 0 {
+            pieces.push(&text[start..i]);
+            start = i;
+        }
+    }
+
+    if start < text.len() {
+        pieces.push(&text[start..]);
+    }
+
+    pieces
+}
+
+/// Line ending used by the content ("\r\n" or "\n")
+pub fn line_ending(content: &str) -> &'static str {
+    if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+/// Push text with surrounding whitespace split off as verbatim segments
+pub fn push_trimmed(segments: &mut Vec<Segment>, text: &str) {
+    let trimmed_start = text.trim_start();
+    let trimmed = trimmed_start.trim_end();
+
+    if trimmed.is_empty() {
+        push_verbatim(segments, text);
+        return;
+    }
+
+    let leading = &text[..text.len() - trimmed_start.len()];
+    let trailing = &trimmed_start[trimmed.len()..];
+
+    push_verbatim(segments, leading);
+    segments.push(Segment::Text(trimmed.to_string()));
+    push_verbatim(segments, trailing);
+}
+
+/// Push text kept as is, merging it with the previous verbatim segment
+pub fn push_verbatim(segments: &mut Vec<Segment>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+
+    match segments.last_mut() {
+        Some(Segment::Verbatim(previous)) => previous.push_str(text),
+        _ => segments.push(Segment::Verbatim(text.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(value: &str) -> Segment {
+        Segment::Text(value.to_string())
+    }
+
+    fn verbatim(value: &str) -> Segment {
+        Segment::Verbatim(value.to_string())
+    }
+
+    #[test]
+    fn test_split_lines() {
+        let segments = split_lines("Hello\r\n\n  indented line \nlast");
+        assert_eq!(
+            segments,
+            vec![
+                text("Hello"),
+                verbatim("\r\n\n  "),
+                text("indented line"),
+                verbatim(" \n"),
+                text("last"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_paragraphs() {
+        let content = "\nFirst line\nsecond line\n\n\nNext paragraph\n";
+        let segments = split_paragraphs(content);
+        assert_eq!(
+            segments,
+            vec![
+                verbatim("\n"),
+                text("First line\nsecond line"),
+                verbatim("\n\n\n"),
+                text("Next paragraph"),
+                verbatim("\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_limit_length() {
+        let content = "First sentence. Second one!\nThird? Fourth.";
+        let segments = limit_length(split_paragraphs(content), 20);
+        assert_eq!(
+            segments,
+            vec![
+                text("First sentence."),
+                verbatim(" "),
+                text("Second one!\nThird?"),
+                verbatim(" "),
+                text("Fourth."),
+            ]
+        );
+        assert_eq!(join(&segments, &[]), content);
+
+        // Long sentences fall back to words, long words to characters
+        let segments = limit_length(vec![text("aaa bbb ccc dddddddd")], 7);
+        assert_eq!(
+            segments,
+            vec![
+                text("aaa bbb"),
+                verbatim(" "),
+                text("ccc"),
+                verbatim(" "),
+                text("ddddddd"),
+                text("d"),
+            ]
+        );
+
+        // Short text and abbreviations-like dots inside words stay intact
+        let segments = limit_length(vec![text("v1.2 is out")], 0);
+        assert_eq!(segments, vec![text("v1.2 is out")]);
+    }
+
+    #[test]
+    fn test_join_keeps_layout() {
+        let content = "one\n\n  two\n";
+        for segments in [split_lines(content), split_paragraphs(content)] {
+            assert_eq!(join(&segments, &[]), content);
+            assert_eq!(
+                join(&segments, &["один".to_string(), "два".to_string()]),
+                "один\n\n  два\n"
+            );
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Glossary term applied to a translation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GlossaryHit {
+    pub term: String,        // Term as found in the source text
+    pub translation: String, // Text put into the translation (the term itself if kept)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct GlossaryEntry {
+    term: String,
+    translation: Option<String>, // None = keep the term untranslated
+}
+
+/// Terminology for one language pair
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Glossary {
+    entries: Vec<GlossaryEntry>, // Longest terms first
+}
+
+impl Glossary {
+    /// One term per line: "term" keeps it untranslated, "term = translation" mandates a translation
+    /// Lines starting with ; or # are comments
+    pub fn parse(content: &str) -> Self {
+        let mut entries: Vec<GlossaryEntry> = Vec::new();
+
+        for line in content.lines() {
+            let line = line.trim().trim_start_matches('\u{feff}');
+            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+                continue;
+            }
+
+            let (term, translation) = match line.split_once('=') {
+                Some((term, translation)) if !translation.trim().is_empty() => {
+                    (term.trim(), Some(translation.trim().to_string()))
+                }
+                Some((term, _)) => (term.trim(), None),
+                None => (line, None),
+            };
+            if term.is_empty() {
+                continue;
+            }
+
+            // A term defined again replaces the earlier definition
+            let term_lower = term.to_lowercase();
+            entries.retain(|entry| entry.term.to_lowercase() != term_lower);
+            entries.push(GlossaryEntry {
+                term: term.to_string(),
+                translation,
+            });
+        }
+
+        // Longer terms win: "API Gateway" before "API"
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.term.chars().count()));
+        Self { entries }
+    }
+
+    /// Glossary files for a language pair in the order they are applied:
+    /// "<to>.txt" for every source language, then "<from>-<to>.txt" for this pair
+    pub fn files(dir: &Path, from: &str, to: &str) -> Vec<PathBuf> {
+        let mut files = vec![dir.join(format!("{}.txt", to))];
+        if from != "auto" {
+            files.push(dir.join(format!("{}-{}.txt", from, to)));
+        }
+        files
+    }
+
+    /// Load and merge existing files; later files override terms of earlier ones
+    pub fn load(files: &[PathBuf]) -> Result<Self, Box<dyn Error>> {
+        let mut content = String::new();
+
+        for file in files.iter().filter(|file| file.is_file()) {
+            let file_content = fs::read_to_string(file)
+                .map_err(|e| format!("Failed to read glossary {}: {}", file.display(), e))?;
+            content.push_str(&file_content);
+            content.push('\n');
+        }
+
+        Ok(Self::parse(&content))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Glossary term at the start of text (whole word, case-insensitive)
+    /// Returns its length in text and the text to put into the translation
+    pub fn match_at(&self, text: &str) -> Option<(usize, String)> {
+        self.entries.iter().find_map(|entry| {
+            let length = match_prefix(text, &entry.term)?;
+            if text[length..].starts_with(char::is_alphanumeric) {
+                return None; // Part of a longer word
+            }
+
+            let matched = &text[..length];
+            let replacement = match &entry.translation {
+                Some(translation) => match_case(matched, &entry.term, translation),
+                None => matched.to_string(),
+            };
+            Some((length, replacement))
+        })
+    }
+}
+
+/// Length of term at the start of text, ignoring case
+fn match_prefix(text: &str, term: &str) -> Option<usize> {
+    let mut text_chars = text.char_indices();
+
+    for term_char in term.chars() {
+        let (_, c) = text_chars.next()?;
+        if !c.to_lowercase().eq(term_char.to_lowercase()) {
+            return None;
+        }
+    }
+
+    Some(text_chars.next().map_or(text.len(), |(i, _)| i))
+}
+
+/// Capitalize the translation when a lowercase term starts a sentence ("Dashboard" -> "Панель")
+fn match_case(matched: &str, term: &str, translation: &str) -> String {
+    let capitalized =
+        matched.starts_with(char::is_uppercase) && term.starts_with(char::is_lowercase);
+    let mut chars = translation.chars();
+
+    match chars.next() {
+        Some(first) if capitalized => first.to_uppercase().chain(chars).collect(),
+        _ => translation.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_match() {
+        let glossary = Glossary::parse(
+            "; Product names\nTagent\nAPI\nAPI Gateway = API-шлюз\ndashboard = панель мониторинга\n\n# empty\n",
+        );
+        assert_eq!(glossary.len(), 4);
+
+        assert_eq!(
+            glossary.match_at("API Gateway settings"),
+            Some((11, "API-шлюз".to_string()))
+        );
+        assert_eq!(glossary.match_at("api calls"), Some((3, "api".to_string())));
+        assert_eq!(
+            glossary.match_at("Dashboard."),
+            Some((9, "Панель мониторинга".to_string()))
+        );
+        assert_eq!(
+            glossary.match_at("tagent's"),
+            Some((6, "tagent".to_string()))
+        );
+
+        // Whole words only
+        assert_eq!(glossary.match_at("APIs"), None);
+        assert_eq!(glossary.match_at("dashboards"), None);
+    }
+
+    #[test]
+    fn test_pair_file_overrides_target_file() {
+        let dir = std::env::temp_dir().join(format!("tagent-glossary-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("ru.txt"), "Tagent\nrelease = выпуск\n").unwrap();
+        fs::write(dir.join("en-ru.txt"), "release = релиз\n").unwrap();
+
+        let files = Glossary::files(&dir, "en", "ru");
+        let glossary = Glossary::load(&files).unwrap();
+        assert_eq!(glossary.len(), 2);
+        assert_eq!(glossary.match_at("release"), Some((7, "релиз".to_string())));
+
+        // Auto-detected source uses only the target file, missing files are skipped
+        assert_eq!(Glossary::files(&dir, "auto", "ru").len(), 1);
+        let glossary = Glossary::load(&Glossary::files(&dir, "auto", "de")).unwrap();
+        assert!(glossary.is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,545 @@
+use crate::config::Config;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Where the translation was made
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryMode {
+    Cli,
+    Interactive,
+    Hotkey,
+    Unknown, // Entries imported from the legacy text file
+}
+
+impl HistoryMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HistoryMode::Cli => "cli",
+            HistoryMode::Interactive => "interactive",
+            HistoryMode::Hotkey => "hotkey",
+            HistoryMode::Unknown => "unknown",
+        }
+    }
+
+    /// Parse mode name used in history filters
+    pub fn parse(value: &str) -> Result<Self, Box<dyn Error>> {
+        match value.trim().to_lowercase().as_str() {
+            "cli" => Ok(HistoryMode::Cli),
+            "interactive" => Ok(HistoryMode::Interactive),
+            "hotkey" | "gui" => Ok(HistoryMode::Hotkey),
+            _ => Err(format!(
+                "Unknown history mode: {} (expected cli, interactive or hotkey)",
+                value
+            )
+            .into()),
+        }
+    }
+}
+
+/// One translation history record (one line of the JSON Lines file)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub mode: HistoryMode,
+    pub provider: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    pub input: String,
+    pub output: String,
+}
+
+impl HistoryEntry {
+    pub fn new(
+        mode: HistoryMode,
+        provider: &str,
+        source_lang: &str,
+        target_lang: &str,
+        input: &str,
+        output: &str,
+    ) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            mode,
+            provider: provider.to_string(),
+            source_lang: source_lang.to_string(),
+            target_lang: target_lang.to_string(),
+            input: input.to_string(),
+            output: output.to_string(),
+        }
+    }
+}
+
+/// Filter for history search, empty filter matches every entry
+#[derive(Debug, Default, Clone)]
+pub struct HistoryFilter {
+    pub query: Option<String>,       // Case-insensitive text in input or output
+    pub since: Option<NaiveDate>,    // First day included (UTC)
+    pub until: Option<NaiveDate>,    // Last day included (UTC)
+    pub source_lang: Option<String>, // Source language code
+    pub target_lang: Option<String>, // Target language code
+    pub mode: Option<HistoryMode>,
+}
+
+impl HistoryFilter {
+    /// Parse "en:ru" language pair, either side may be empty or "*"
+    pub fn set_language_pair(&mut self, pair: &str) -> Result<(), Box<dyn Error>> {
+        let (source, target) = pair
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid language pair: {} (expected e.g. en:ru)", pair))?;
+
+        let language = |code: &str| match code.trim() {
+            "" | "*" => None,
+            code => Some(code.to_lowercase()),
+        };
+
+        self.source_lang = language(source);
+        self.target_lang = language(target);
+        Ok(())
+    }
+
+    pub fn matches(&self, entry: &HistoryEntry) -> bool {
+        let date = entry.timestamp.date_naive();
+
+        if self.since.is_some_and(|since| date < since)
+            || self.until.is_some_and(|until| date > until)
+        {
+            return false;
+        }
+
+        if self
+            .source_lang
+            .as_ref()
+            .is_some_and(|lang| !entry.source_lang.eq_ignore_ascii_case(lang))
+            || self
+                .target_lang
+                .as_ref()
+                .is_some_and(|lang| !entry.target_lang.eq_ignore_ascii_case(lang))
+        {
+            return false;
+        }
+
+        if self.mode.is_some_and(|mode| entry.mode != mode) {
+            return false;
+        }
+
+        match &self.query {
+            Some(query) => {
+                let query = query.to_lowercase();
+                entry.input.to_lowercase().contains(&query)
+                    || entry.output.to_lowercase().contains(&query)
+            }
+            None => true,
+        }
+    }
+}
+
+/// Parse history search arguments, shared by --history and /history
+/// args: [query] [--since DATE] [--until DATE] [--pair en:ru] [--mode MODE] [--limit N]
+/// Returns the filter and the number of entries to show (default 20, 0 = all)
+pub fn parse_search(args: &[String]) -> Result<(HistoryFilter, usize), Box<dyn Error>> {
+    let mut filter = HistoryFilter::default();
+    let mut limit = 20;
+    let mut query_words = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+
+        if matches!(arg, "--since" | "--until" | "--pair" | "--mode" | "--limit") {
+            let value = args
+                .get(i + 1)
+                .ok_or_else(|| format!("Missing value for {}", arg))?;
+
+            match arg {
+                "--since" => filter.since = Some(parse_date(value)?),
+                "--until" => filter.until = Some(parse_date(value)?),
+                "--pair" => filter.set_language_pair(value)?,
+                "--mode" => filter.mode = Some(HistoryMode::parse(value)?),
+                _ => {
+                    limit = value
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid limit: {}", value))?
+                }
+            }
+            i += 2;
+        } else {
+            query_words.push(arg);
+            i += 1;
+        }
+    }
+
+    if !query_words.is_empty() {
+        filter.query = Some(query_words.join(" "));
+    }
+
+    Ok((filter, limit))
+}
+
+/// Parse date used in history filters (YYYY-MM-DD)
+pub fn parse_date(value: &str) -> Result<NaiveDate, Box<dyn Error>> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date: {} (expected YYYY-MM-DD)", value).into())
+}
+
+/// Search entries, newest first
+/// Each result carries its history number: 1 = most recent entry
+/// Numbers do not depend on the filter, so they can be used with /h and /again
+pub fn search<'a>(
+    entries: &'a [HistoryEntry],
+    filter: &HistoryFilter,
+) -> Vec<(usize, &'a HistoryEntry)> {
+    entries
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(index, entry)| (index + 1, entry))
+        .filter(|(_, entry)| filter.matches(entry))
+        .collect()
+}
+
+/// Get entry by history number (1 = most recent)
+pub fn get_by_number(entries: &[HistoryEntry], number: usize) -> Option<&HistoryEntry> {
+    if number == 0 {
+        return None;
+    }
+    entries.iter().rev().nth(number - 1)
+}
+
+/// Format entry as a single line for history lists
+pub fn format_summary(number: usize, entry: &HistoryEntry) -> String {
+    format!(
+        "{:>4}  {}  {} -> {}  [{}]  {} => {}",
+        number,
+        entry.timestamp.format("%Y-%m-%d %H:%M"),
+        entry.source_lang,
+        entry.target_lang,
+        entry.mode.as_str(),
+        shorten(&entry.input, 40),
+        shorten(&entry.output, 40)
+    )
+}
+
+/// Format entry with full input and output
+pub fn format_details(number: usize, entry: &HistoryEntry) -> String {
+    let mut header = format!(
+        "#{} {} {} -> {} [{}]",
+        number,
+        entry.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+        entry.source_lang,
+        entry.target_lang,
+        entry.mode.as_str()
+    );
+    if !entry.provider.is_empty() {
+        header.push_str(&format!(" ({})", entry.provider));
+    }
+
+    format!("{}\nIN:  {}\nOUT: {}", header, entry.input, entry.output)
+}
+
+/// First line of text limited to max_chars characters
+fn shorten(text: &str, max_chars: usize) -> String {
+    let first_line = text.lines().next().unwrap_or("");
+    let multi_line = text.lines().nth(1).is_some();
+
+    if first_line.chars().count() > max_chars {
+        let cut: String = first_line.chars().take(max_chars - 3).collect();
+        format!("{}...", cut)
+    } else if multi_line {
+        format!("{} ...", first_line)
+    } else {
+        first_line.to_string()
+    }
+}
+
+/// Get history file path from config
+/// A legacy ".txt" HistoryFile setting is stored next to it as ".jsonl"
+pub fn history_path(config: &Config) -> PathBuf {
+    let path = PathBuf::from(&config.history_file);
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("txt") => path.with_extension("jsonl"),
+        _ => path,
+    }
+}
+
+/// Save translation to history file if history is enabled
+pub fn save(config: &Config, entry: &HistoryEntry) -> Result<(), Box<dyn Error>> {
+    if !config.save_translation_history {
+        return Ok(()); // История отключена
+    }
+
+    let path = history_path(config);
+
+    // First write: bring over entries from the old text format
+    if !path.exists() {
+        import_legacy(&path.with_extension("txt"), &path)?;
+    }
+
+    append(&path, entry)
+}
+
+/// Load all history entries, skipping malformed lines
+pub fn load(config: &Config) -> Result<Vec<HistoryEntry>, Box<dyn Error>> {
+    let path = history_path(config);
+
+    if !path.exists() {
+        // History was never written in the new format, read the legacy file
+        let legacy_path = path.with_extension("txt");
+        if legacy_path.exists() {
+            return Ok(parse_legacy(&fs::read_to_string(legacy_path)?));
+        }
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)?;
+
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Append one entry as a JSON line
+fn append(path: &Path, entry: &HistoryEntry) -> Result<(), Box<dyn Error>> {
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    file.write_all(line.as_bytes())?;
+    file.flush()?; // Принудительно записываем на диск
+
+    Ok(())
+}
+
+/// Convert legacy translation_history.txt into JSON Lines file
+/// The legacy file itself is left untouched
+/// Returns number of imported entries
+fn import_legacy(legacy_path: &Path, path: &Path) -> Result<usize, Box<dyn Error>> {
+    if !legacy_path.exists() {
+        return Ok(0);
+    }
+
+    let entries = parse_legacy(&fs::read_to_string(legacy_path)?);
+    for entry in &entries {
+        append(path, entry)?;
+    }
+
+    Ok(entries.len())
+}
+
+/// Parse legacy history format:
+///
+/// [2025-09-06 14:30:15 UTC] en -> ru (Google Translate)
+/// IN:  hello
+/// OUT: привет
+/// ---
+fn parse_legacy(content: &str) -> Vec<HistoryEntry> {
+    let mut entries = Vec::new();
+    let mut current: Option<HistoryEntry> = None;
+    let mut in_output = false;
+
+    for line in content.lines() {
+        if line == "---" {
+            if let Some(mut entry) = current.take() {
+                entry.input = entry.input.trim_end().to_string();
+                entry.output = entry.output.trim_end().to_string();
+                entries.push(entry);
+            }
+            in_output = false;
+            continue;
+        }
+
+        match current.as_mut() {
+            None => {
+                if let Some(entry) = parse_legacy_header(line) {
+                    current = Some(entry);
+                }
+            }
+            Some(entry) => {
+                if let Some(input) = line.strip_prefix("IN:  ") {
+                    entry.input = input.to_string();
+                } else if let Some(output) = line.strip_prefix("OUT: ") {
+                    entry.output = output.to_string();
+                    in_output = true;
+                } else if in_output {
+                    // Multi-line output (dictionary entries)
+                    entry.output.push('\n');
+                    entry.output.push_str(line);
+                } else {
+                    entry.input.push('\n');
+                    entry.input.push_str(line);
+                }
+            }
+        }
+    }
+
+    entries
+}
+
+/// Parse "[2025-09-06 14:30:15 UTC] en -> ru" header with optional "(Provider)"
+fn parse_legacy_header(line: &str) -> Option<HistoryEntry> {
+    let rest = line.strip_prefix('[')?;
+    let (time, rest) = rest.split_once("] ")?;
+
+    let timestamp =
+        NaiveDateTime::parse_from_str(time.trim_end_matches(" UTC"), "%Y-%m-%d %H:%M:%S")
+            .ok()?
+            .and_utc();
+
+    let (languages, provider) = match rest.split_once(" (") {
+        Some((languages, provider)) => (languages, provider.trim_end_matches(')')),
+        None => (rest, ""),
+    };
+    let (source_lang, target_lang) = languages.split_once(" -> ")?;
+
+    Some(HistoryEntry {
+        timestamp,
+        mode: HistoryMode::Unknown,
+        provider: provider.to_string(),
+        source_lang: source_lang.trim().to_string(),
+        target_lang: target_lang.trim().to_string(),
+        input: String::new(),
+        output: String::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEGACY: &str = "[2025-09-06 14:30:15 UTC] en -> ru\n\
+        IN:  hello\n\
+        OUT: привет\n\
+        ---\n\
+        \n\
+        [2025-09-06 14:32:45 UTC] en -> ru (Google Translate)\n\
+        IN:  cat\n\
+        OUT: Существительное\n  кот [кошка, котенок]\n\
+        ---\n\n";
+
+    #[test]
+    fn test_parse_legacy() {
+        let entries = parse_legacy(LEGACY);
+        assert_eq!(entries.len(), 2);
+
+        assert_eq!(entries[0].input, "hello");
+        assert_eq!(entries[0].output, "привет");
+        assert_eq!(entries[0].provider, "");
+        assert_eq!(entries[0].mode, HistoryMode::Unknown);
+        assert_eq!(
+            entries[0].timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+            "2025-09-06 14:30:15"
+        );
+
+        assert_eq!(entries[1].provider, "Google Translate");
+        assert_eq!(entries[1].output, "Существительное\n  кот [кошка, котенок]");
+    }
+
+    #[test]
+    fn test_search_filters() {
+        let mut entries = parse_legacy(LEGACY);
+        entries.push(HistoryEntry::new(
+            HistoryMode::Hotkey,
+            "DeepL",
+            "ru",
+            "en",
+            "кошка",
+            "cat",
+        ));
+
+        // Numbers count from the most recent entry
+        let all = search(&entries, &HistoryFilter::default());
+        assert_eq!(
+            all.iter().map(|(n, _)| *n).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(all[0].1.input, "кошка");
+
+        let filter = HistoryFilter {
+            query: Some("КОТ".to_string()),
+            ..HistoryFilter::default()
+        };
+        let found = search(&entries, &filter);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, 2);
+        assert_eq!(get_by_number(&entries, 2).unwrap().input, "cat");
+
+        let mut filter = HistoryFilter {
+            mode: Some(HistoryMode::parse("hotkey").unwrap()),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(search(&entries, &filter).len(), 1);
+
+        filter.mode = None;
+        filter.set_language_pair("en:*").unwrap();
+        filter.since = Some(parse_date("2025-09-06").unwrap());
+        filter.until = Some(parse_date("2025-09-06").unwrap());
+        assert_eq!(search(&entries, &filter).len(), 2);
+
+        assert!(filter.set_language_pair("en-ru").is_err());
+        assert!(parse_date("06.09.2025").is_err());
+    }
+
+    #[test]
+    fn test_parse_search() {
+        let args = |line: &str| {
+            line.split_whitespace()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        };
+
+        let (filter, limit) = parse_search(&args("black cat --pair en:ru --limit 0")).unwrap();
+        assert_eq!(filter.query.as_deref(), Some("black cat"));
+        assert_eq!(filter.source_lang.as_deref(), Some("en"));
+        assert_eq!(filter.target_lang.as_deref(), Some("ru"));
+        assert_eq!(limit, 0);
+
+        let (filter, limit) =
+            parse_search(&args("--mode hotkey --since 2025-09-01 --until 2025-09-30")).unwrap();
+        assert_eq!(filter.query, None);
+        assert_eq!(filter.mode, Some(HistoryMode::Hotkey));
+        assert_eq!(filter.until, Some(parse_date("2025-09-30").unwrap()));
+        assert_eq!(limit, 20);
+
+        assert!(parse_search(&args("cat --since")).is_err());
+        assert!(parse_search(&args("--limit all")).is_err());
+    }
+
+    #[test]
+    fn test_save_imports_legacy_file() {
+        let dir = std::env::temp_dir().join(format!("tagent_history_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let legacy_path = dir.join("translation_history.txt");
+        fs::write(&legacy_path, LEGACY).unwrap();
+
+        let config = Config {
+            save_translation_history: true,
+            history_file: legacy_path.to_string_lossy().to_string(),
+            ..Config::default()
+        };
+
+        let entry = HistoryEntry::new(
+            HistoryMode::Cli,
+            "DeepL",
+            "en",
+            "ru",
+            "line one\nline two",
+            "строка один\nстрока два",
+        );
+        save(&config, &entry).unwrap();
+
+        let entries = load(&config).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[2], entry);
+        assert!(dir.join("translation_history.jsonl").exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
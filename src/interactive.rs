@@ -1,12 +1,15 @@
 // interactive.rs
 use crate::cli::CliHandler;
 use crate::config::ConfigManager;
+use crate::history::{self, HistoryEntry, HistoryMode};
+use crate::providers::cache::TranslationCache;
+use crate::quiz::{self, AnswerGrade};
 use crate::speech::SpeechManager;
 use crate::translator::Translator;
-use chrono::{DateTime, Utc};
+use crate::vocabulary::{self, Vocabulary};
+use chrono::{Local, Utc};
 use colored::Colorize;
 use std::error::Error;
-use std::fs::OpenOptions;
 use std::io::{self, Write};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -24,7 +27,7 @@ impl InteractiveMode {
         let config_path = ConfigManager::get_default_config_path()?;
         let config_manager = Arc::new(ConfigManager::new(config_path.to_string_lossy().as_ref())?);
         let should_exit = Arc::new(AtomicBool::new(false));
-        let speech_manager = SpeechManager::new();
+        let speech_manager = SpeechManager::new(&config_manager.get_config())?;
 
         Ok(Self {
             translator,
@@ -38,38 +41,6 @@ impl InteractiveMode {
         self.should_exit.clone()
     }
 
-    /// Save translation history to file (Interactive version)
-    fn save_translation_history(
-        &self,
-        original: &str,
-        translated: &str,
-        source_lang: &str,
-        target_lang: &str,
-        config: &crate::config::Config,
-    ) -> Result<(), Box<dyn Error>> {
-        if !config.save_translation_history {
-            return Ok(()); // История отключена
-        }
-
-        let timestamp: DateTime<Utc> = Utc::now();
-        let formatted_time = timestamp.format("%Y-%m-%d %H:%M:%S UTC");
-
-        let entry = format!(
-            "[{}] {} -> {}\nIN:  {}\nOUT: {}\n---\n\n",
-            formatted_time, source_lang, target_lang, original, translated
-        );
-
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&config.history_file)?;
-
-        file.write_all(entry.as_bytes())?;
-        file.flush()?; // Принудительно записываем на диск
-
-        Ok(())
-    }
-
     /// Start interactive translation mode (unified with GUI)
     pub async fn start(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
         loop {
@@ -82,7 +53,6 @@ impl InteractiveMode {
             // Check if config file was modified and reload if necessary
             self.config_manager.check_and_reload().ok();
             let config = self.config_manager.get_config();
-            let (source_code, target_code) = self.config_manager.get_language_codes();
 
             // Show colored prompt
             let prompt = format!("[{}]: ", config.source_language);
@@ -110,10 +80,7 @@ impl InteractiveMode {
 
                     // If not a command, try to translate the text
                     if !text.is_empty() {
-                        if let Err(e) = self
-                            .translate_interactive_text(text, &source_code, &target_code, &config)
-                            .await
-                        {
+                        if let Err(e) = self.translate_input(text, &config).await {
                             println!("Translation error: {}", e);
                         }
                     }
@@ -150,6 +117,21 @@ impl InteractiveMode {
             }
             println!(); // Add spacing
             Ok(true)
+        } else if let Some(query) = text.strip_prefix("/history ") {
+            self.show_history(query.trim());
+            Ok(true)
+        } else if let Some(number) = text.strip_prefix("/h ") {
+            if let Err(e) = self.recall_history(number) {
+                println!("History error: {}", e);
+                println!();
+            }
+            Ok(true)
+        } else if let Some(number) = text.strip_prefix("/again ") {
+            if let Err(e) = self.translate_again(number).await {
+                println!("History error: {}", e);
+                println!();
+            }
+            Ok(true)
         } else {
             match text {
                 "" => Ok(true), // Skip empty lines
@@ -182,6 +164,36 @@ impl InteractiveMode {
                     Ok(true)
                 }
 
+                // History commands
+                "/history" => {
+                    self.show_history("");
+                    Ok(true)
+                }
+
+                // Vocabulary quiz
+                "/quiz" => {
+                    if let Err(e) = self.run_quiz() {
+                        println!("Quiz error: {}", e);
+                        println!();
+                    }
+                    Ok(true)
+                }
+
+                // Translation cache commands
+                "/cache" => {
+                    if let Err(e) = self.show_cache_status() {
+                        println!("Cache error: {}", e);
+                    }
+                    Ok(true)
+                }
+
+                "/cache clear" => {
+                    if let Err(e) = self.clear_cache() {
+                        println!("Cache error: {}", e);
+                    }
+                    Ok(true)
+                }
+
                 // Clear screen commands (only with slash)
                 "/clear" | "/cls" => {
                     print!("\x1B[2J\x1B[1;1H");
@@ -212,6 +224,241 @@ impl InteractiveMode {
             .map_err(|e| format!("Config display error: {}", e))
     }
 
+    /// Load history entries with current [History] settings
+    fn load_history(&self) -> Result<Vec<HistoryEntry>, String> {
+        self.config_manager.check_and_reload().ok();
+        history::load(&self.config_manager.get_config())
+            .map_err(|e| format!("Failed to read history: {}", e))
+    }
+
+    /// Show recent history entries, optionally filtered like --history
+    /// args: [text] [--since DATE] [--until DATE] [--pair en:ru] [--mode MODE] [--limit N]
+    fn show_history(&self, args: &str) {
+        let args: Vec<String> = args.split_whitespace().map(String::from).collect();
+        let (filter, limit) = match history::parse_search(&args) {
+            Ok(search) => search,
+            Err(e) => {
+                println!("History error: {}", e);
+                println!();
+                return;
+            }
+        };
+
+        let entries = match self.load_history() {
+            Ok(entries) => entries,
+            Err(e) => {
+                println!("{}", e);
+                return;
+            }
+        };
+
+        CliHandler::print_history(&entries, &filter, limit);
+    }
+
+    /// Get history entry by number typed after /h or /again
+    fn history_entry(&self, number: &str) -> Result<(usize, HistoryEntry), String> {
+        let number = number
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| format!("Invalid history number: {}", number.trim()))?;
+
+        let entries = self.load_history()?;
+        history::get_by_number(&entries, number)
+            .map(|entry| (number, entry.clone()))
+            .ok_or_else(|| format!("No history entry #{} (see /history)", number))
+    }
+
+    /// Show full history entry (/h <n>)
+    fn recall_history(&self, number: &str) -> Result<(), String> {
+        let (number, entry) = self.history_entry(number)?;
+        println!("{}", history::format_details(number, &entry));
+        println!();
+        Ok(())
+    }
+
+    /// Translate input of a history entry again with current settings (/again <n>)
+    async fn translate_again(&self, number: &str) -> Result<(), String> {
+        let (_, entry) = self.history_entry(number)?;
+
+        let config = self.config_manager.get_config();
+        println!("{}", entry.input);
+        self.translate_input(&entry.input, &config).await
+    }
+
+    /// Spaced repetition quiz over words collected from dictionary lookups
+    fn run_quiz(&self) -> Result<(), String> {
+        let path = ConfigManager::get_vocabulary_path(&self.config_manager.get_config());
+        let vocabulary = Vocabulary::load(&path).map_err(|e| e.to_string())?;
+
+        if vocabulary.words.is_empty() {
+            println!("Vocabulary list is empty");
+            println!("Look up single words to add them to the quiz");
+            println!();
+            return Ok(());
+        }
+
+        let due = quiz::due_words(&vocabulary.words, Utc::now(), quiz::SESSION_SIZE);
+        if due.is_empty() {
+            if let Some(next) = quiz::next_review(&vocabulary.words) {
+                println!(
+                    "No words due for review. Next review: {}",
+                    next.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+                );
+            }
+            println!();
+            return Ok(());
+        }
+
+        let config = self.config_manager.get_config();
+        println!("=== Quiz: {} words ===", due.len());
+        println!("Type the translation and press Enter ('?' = don't know, /q = stop)");
+        println!();
+
+        let mut answered = 0;
+        let mut correct = 0;
+
+        for (number, &index) in due.iter().enumerate() {
+            let word = &vocabulary.words[index];
+
+            let prompt = format!(
+                "[{}/{}] {} ({} -> {}): ",
+                number + 1,
+                due.len(),
+                word.word,
+                word.source_lang,
+                word.target_lang
+            );
+            if let Some(color) = ConfigManager::parse_color(&config.source_prompt_color) {
+                print!("{}", prompt.color(color));
+            } else {
+                print!("{}", prompt);
+            }
+            io::stdout()
+                .flush()
+                .map_err(|e| format!("IO error: {}", e))?;
+
+            let mut answer = String::new();
+            io::stdin()
+                .read_line(&mut answer)
+                .map_err(|e| format!("Input error: {}", e))?;
+            let answer = answer.trim();
+
+            if answer == "/q" {
+                break;
+            }
+
+            let grade = quiz::grade_answer(word, answer);
+            answered += 1;
+
+            let translations = word
+                .definitions
+                .iter()
+                .flat_map(|pos_entry| pos_entry.definitions.iter())
+                .map(vocabulary::format_definition)
+                .collect::<Vec<_>>()
+                .join("; ");
+
+            match grade {
+                AnswerGrade::Correct => {
+                    correct += 1;
+                    println!("{} {}", "Correct!".green(), translations);
+                }
+                AnswerGrade::Close => {
+                    correct += 1;
+                    println!("{} {}", "Almost:".yellow(), translations);
+                }
+                AnswerGrade::DontKnow | AnswerGrade::Wrong => {
+                    println!("{} {}", "Answer:".red(), translations);
+                }
+            }
+
+            // Save after every answer; reload first to keep words added by the hotkey meanwhile
+            let mut current = Vocabulary::load(&path).map_err(|e| e.to_string())?;
+            if let Some(stored) = current.find_mut(&word.word, &word.source_lang, &word.target_lang)
+            {
+                stored.review.review(grade.quality(), Utc::now());
+                println!("Next review in {} day(s)", stored.review.interval_days);
+            }
+            current.save(&path).map_err(|e| e.to_string())?;
+            println!();
+        }
+
+        println!("Quiz finished: {}/{} correct", correct, answered);
+        println!();
+        Ok(())
+    }
+
+    /// Open translation cache with current [Cache] settings
+    fn open_cache(&self) -> Result<Arc<TranslationCache>, String> {
+        let config = self.config_manager.get_config();
+        Ok(TranslationCache::open(
+            &ConfigManager::get_cache_path(&config),
+            config.cache_ttl_hours,
+            config.cache_max_entries,
+        ))
+    }
+
+    /// Show number of cached entries and cache file location
+    fn show_cache_status(&self) -> Result<(), String> {
+        let config = self.config_manager.get_config();
+        let cache = self.open_cache()?;
+
+        println!(
+            "Translation cache: {}",
+            if config.enable_cache {
+                "Enabled"
+            } else {
+                "Disabled"
+            }
+        );
+        println!("Cached entries: {}", cache.len());
+        println!("Cache file: {}", cache.path().display());
+        println!();
+        Ok(())
+    }
+
+    /// Remove all cached translations and dictionary entries
+    fn clear_cache(&self) -> Result<(), String> {
+        let removed = self
+            .open_cache()?
+            .clear()
+            .map_err(|e| format!("Failed to clear cache: {}", e))?;
+
+        println!("Translation cache cleared ({} entries removed)", removed);
+        println!();
+        Ok(())
+    }
+
+    /// Translate input into the target language, or each of several target languages
+    async fn translate_input(
+        &self,
+        text: &str,
+        config: &crate::config::Config,
+    ) -> Result<(), String> {
+        let (source_code, target_codes) = self.translator.target_codes_for(text).await;
+
+        if let [target_code] = target_codes.as_slice() {
+            return self
+                .translate_interactive_text(text, &source_code, target_code, config)
+                .await;
+        }
+
+        // Several target languages: one block per language, no dictionary
+        let results = self
+            .translator
+            .translate_to_targets(text, &source_code, &target_codes)
+            .await;
+        self.translator.print_target_translations(
+            text,
+            &source_code,
+            &results,
+            config,
+            HistoryMode::Interactive,
+        );
+        println!(); // Add spacing
+        Ok(())
+    }
+
     /// Translate text in interactive mode
     async fn translate_interactive_text(
         &self,
@@ -227,7 +474,9 @@ impl InteractiveMode {
                 .get_dictionary_entry_public(text, source_code, target_code)
                 .await
             {
-                Ok(dictionary_info) => {
+                Ok(dictionary) => {
+                    let dictionary_info = dictionary.text;
+
                     // Print colored dictionary label
                     let dict_label = "[Word]: ";
                     if let Some(color) = ConfigManager::parse_color(&config.dictionary_prompt_color)
@@ -237,6 +486,9 @@ impl InteractiveMode {
                         print!("{}", dict_label);
                     }
                     println!("{}", dictionary_info);
+                    if let Some(note) = self.translator.fallback_note(&dictionary.provider) {
+                        println!("{}", note.dimmed());
+                    }
 
                     if config.copy_to_clipboard {
                         if let Err(e) = self.copy_to_clipboard(&dictionary_info) {
@@ -245,13 +497,15 @@ impl InteractiveMode {
                     }
 
                     // Сохраняем словарную статью в историю
-                    if let Err(e) = self.save_translation_history(
-                        text,
-                        &dictionary_info,
+                    let entry = HistoryEntry::new(
+                        HistoryMode::Interactive,
+                        &dictionary.provider,
                         source_code,
                         target_code,
-                        config,
-                    ) {
+                        text,
+                        &dictionary_info,
+                    );
+                    if let Err(e) = history::save(config, &entry) {
                         println!("History save error: {}", e);
                     }
 
@@ -270,28 +524,39 @@ impl InteractiveMode {
             .translate_text_public(text, source_code, target_code)
             .await
         {
-            Ok(translated_text) => {
+            Ok(translation) => {
+                let translated_text = translation.text;
+
                 // Print colored translation label
-                let trans_label = format!("[{}]: ", config.target_language);
+                let trans_label =
+                    format!("[{}]: ", ConfigManager::language_name(config, target_code));
                 if let Some(color) = ConfigManager::parse_color(&config.target_prompt_color) {
                     print!("{}", trans_label.color(color));
                 } else {
                     print!("{}", trans_label);
                 }
                 println!("{}", translated_text);
+                if let Some(note) = self.translator.fallback_note(&translation.provider) {
+                    println!("{}", note.dimmed());
+                }
+                for warning in &translation.warnings {
+                    println!("{}", format!("Warning: {}", warning).dimmed());
+                }
 
                 if config.copy_to_clipboard {
                     self.copy_to_clipboard(&translated_text).ok();
                 }
 
                 // Сохраняем перевод в историю
-                if let Err(e) = self.save_translation_history(
-                    text,
-                    &translated_text,
+                let entry = HistoryEntry::new(
+                    HistoryMode::Interactive,
+                    &translation.provider,
                     source_code,
                     target_code,
-                    config,
-                ) {
+                    text,
+                    &translated_text,
+                );
+                if let Err(e) = history::save(config, &entry) {
                     println!("History save error: {}", e);
                 }
             }
@@ -334,13 +599,16 @@ impl InteractiveMode {
 
         // Load current configuration to get source language
         self.config_manager.check_and_reload().ok();
-        let (source_code, _) = self.config_manager.get_language_codes();
+        let (source_code, _) = self.translator.language_codes_for(text).await;
 
-        // If source language is "auto", use English by default for TTS
+        // If source language is "auto", detect it; English if detection fails
         let speech_lang = if source_code == "auto" {
-            "en"
+            self.translator
+                .detect_language(text)
+                .await
+                .unwrap_or_else(|| "en".to_string())
         } else {
-            &source_code
+            source_code
         };
 
         let config = self.config_manager.get_config();
@@ -374,7 +642,7 @@ impl InteractiveMode {
         // Start speech with cancellation support
         let speech_result = self
             .speech_manager
-            .speak_text_with_cancel(text, speech_lang, stop_flag.clone())
+            .speak_text_with_cancel(text, &speech_lang, stop_flag.clone())
             .await;
 
         // Cancel the Esc monitor task
@@ -291,7 +291,7 @@ unsafe fn trigger_speech() {
 
 /// Speak text from clipboard
 async fn speak_clipboard(
-    _translator: &Translator,
+    translator: &Translator,
     stop_flag: Arc<AtomicBool>,
 ) -> Result<(), Box<dyn Error>> {
     use crate::clipboard::ClipboardManager;
@@ -352,15 +352,17 @@ async fn speak_clipboard(
     }
 
     // Detect or use source language
-    let (source_code, _target_code) = config_manager.get_language_codes();
+    let (source_code, _target_code) = translator.language_codes_for(&text).await;
 
     // Use auto-detected language or source language for speech
     let lang_code = if source_code == "auto" {
-        // Try to detect language from text
-        // For now, default to English if auto
-        "en"
+        // English if the language cannot be detected
+        translator
+            .detect_language(&text)
+            .await
+            .unwrap_or_else(|| "en".to_string())
     } else {
-        &source_code
+        source_code
     };
 
     // Clear any existing prompt and print speech info
@@ -377,9 +379,9 @@ async fn speak_clipboard(
     println!("{}", text);
 
     // Call speech directly (blocking until completion or cancellation)
-    let speech_manager = SpeechManager::new();
+    let speech_manager = SpeechManager::new(&config)?;
     match speech_manager
-        .speak_text_with_cancel(&text, lang_code, stop_flag)
+        .speak_text_with_cancel(&text, &lang_code, stop_flag)
         .await
     {
         Ok(_) => {
@@ -0,0 +1,331 @@
+use crate::quiz::edit_distance;
+
+/// Writing system of a language
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Script {
+    Latin,
+    Cyrillic,
+    Arabic,
+    Greek,
+    Hebrew,
+    Armenian,
+    Georgian,
+    Ethiopic,
+    Devanagari,
+    Bengali,
+    Gurmukhi,
+    Gujarati,
+    Tamil,
+    Telugu,
+    Kannada,
+    Malayalam,
+    Sinhala,
+    Thai,
+    Lao,
+    Khmer,
+    Myanmar,
+    Hangul,
+    Kana, // Japanese: kana together with kanji
+    Han,
+}
+
+/// Language known to the translator
+#[derive(Debug)]
+pub struct Language {
+    pub code: &'static str,   // ISO 639-1, region for variants ("zh-TW")
+    pub code3: &'static str,  // ISO 639-3
+    pub name: &'static str,   // English name
+    pub native: &'static str, // Name in the language itself
+    pub aliases: &'static [&'static str], // Other names and legacy codes
+    pub script: Script,       // Writing system
+    pub provider_codes: &'static [(&'static str, &'static str)], // Provider -> code if it differs
+}
+
+const fn language(
+    code: &'static str,
+    code3: &'static str,
+    name: &'static str,
+    native: &'static str,
+    script: Script,
+) -> Language {
+    Language {
+        code,
+        code3,
+        name,
+        native,
+        aliases: &[],
+        script,
+        provider_codes: &[],
+    }
+}
+
+/// All languages by English name; the first language of a script is its default for detection
+#[rustfmt::skip]
+pub const LANGUAGES: &[Language] = &[
+    language("af", "afr", "Afrikaans", "Afrikaans", Script::Latin),
+    language("sq", "sqi", "Albanian", "Shqip", Script::Latin),
+    language("am", "amh", "Amharic", "አማርኛ", Script::Ethiopic),
+    language("ar", "ara", "Arabic", "العربية", Script::Arabic),
+    language("hy", "hye", "Armenian", "Հայերեն", Script::Armenian),
+    language("az", "aze", "Azerbaijani", "Azərbaycan", Script::Latin),
+    language("eu", "eus", "Basque", "Euskara", Script::Latin),
+    language("be", "bel", "Belarusian", "Беларуская", Script::Cyrillic),
+    Language { aliases: &["bangla"], ..language("bn", "ben", "Bengali", "বাংলা", Script::Bengali) },
+    language("bs", "bos", "Bosnian", "Bosanski", Script::Latin),
+    language("bg", "bul", "Bulgarian", "Български", Script::Cyrillic),
+    Language { aliases: &["myanmar"], ..language("my", "mya", "Burmese", "မြန်မာ", Script::Myanmar) },
+    language("ca", "cat", "Catalan", "Català", Script::Latin),
+    language("ceb", "ceb", "Cebuano", "Cebuano", Script::Latin),
+    Language { aliases: &["chewa", "nyanja"], ..language("ny", "nya", "Chichewa", "Chichewa", Script::Latin) },
+    Language {
+        aliases: &["zh-cn", "zh-hans", "simplified chinese", "chinese simplified", "mandarin"],
+        provider_codes: &[("google", "zh-CN"), ("deepl", "ZH-HANS")],
+        ..language("zh", "zho", "Chinese", "中文", Script::Han)
+    },
+    Language {
+        aliases: &["zh-hant", "zt", "traditional chinese", "chinese traditional"],
+        provider_codes: &[("deepl", "ZH-HANT"), ("libretranslate", "zt")],
+        ..language("zh-TW", "zho", "Chinese (Traditional)", "繁體中文", Script::Han)
+    },
+    language("co", "cos", "Corsican", "Corsu", Script::Latin),
+    language("hr", "hrv", "Croatian", "Hrvatski", Script::Latin),
+    language("cs", "ces", "Czech", "Čeština", Script::Latin),
+    language("da", "dan", "Danish", "Dansk", Script::Latin),
+    Language { aliases: &["flemish"], ..language("nl", "nld", "Dutch", "Nederlands", Script::Latin) },
+    Language {
+        aliases: &["en-us", "en-gb"],
+        provider_codes: &[("deepl", "EN-US")],
+        ..language("en", "eng", "English", "English", Script::Latin)
+    },
+    language("eo", "epo", "Esperanto", "Esperanto", Script::Latin),
+    language("et", "est", "Estonian", "Eesti", Script::Latin),
+    language("fi", "fin", "Finnish", "Suomi", Script::Latin),
+    language("fr", "fra", "French", "Français", Script::Latin),
+    Language { aliases: &["west frisian"], ..language("fy", "fry", "Frisian", "Frysk", Script::Latin) },
+    language("gl", "glg", "Galician", "Galego", Script::Latin),
+    language("ka", "kat", "Georgian", "ქართული", Script::Georgian),
+    language("de", "deu", "German", "Deutsch", Script::Latin),
+    language("el", "ell", "Greek", "Ελληνικά", Script::Greek),
+    language("gu", "guj", "Gujarati", "ગુજરાતી", Script::Gujarati),
+    Language { aliases: &["haitian"], ..language("ht", "hat", "Haitian Creole", "Kreyòl ayisyen", Script::Latin) },
+    language("ha", "hau", "Hausa", "Hausa", Script::Latin),
+    language("haw", "haw", "Hawaiian", "ʻŌlelo Hawaiʻi", Script::Latin),
+    Language {
+        aliases: &["iw"],
+        provider_codes: &[("google", "iw")],
+        ..language("he", "heb", "Hebrew", "עברית", Script::Hebrew)
+    },
+    language("hi", "hin", "Hindi", "हिन्दी", Script::Devanagari),
+    language("hmn", "hmn", "Hmong", "Hmoob", Script::Latin),
+    language("hu", "hun", "Hungarian", "Magyar", Script::Latin),
+    language("is", "isl", "Icelandic", "Íslenska", Script::Latin),
+    language("ig", "ibo", "Igbo", "Igbo", Script::Latin),
+    language("id", "ind", "Indonesian", "Bahasa Indonesia", Script::Latin),
+    language("ga", "gle", "Irish", "Gaeilge", Script::Latin),
+    language("it", "ita", "Italian", "Italiano", Script::Latin),
+    language("ja", "jpn", "Japanese", "日本語", Script::Kana),
+    Language {
+        aliases: &["jw"],
+        provider_codes: &[("google", "jw")],
+        ..language("jv", "jav", "Javanese", "Basa Jawa", Script::Latin)
+    },
+    language("kn", "kan", "Kannada", "ಕನ್ನಡ", Script::Kannada),
+    language("kk", "kaz", "Kazakh", "Қазақ тілі", Script::Cyrillic),
+    Language { aliases: &["cambodian"], ..language("km", "khm", "Khmer", "ខ្មែរ", Script::Khmer) },
+    language("rw", "kin", "Kinyarwanda", "Kinyarwanda", Script::Latin),
+    language("ko", "kor", "Korean", "한국어", Script::Hangul),
+    language("ku", "kur", "Kurdish", "Kurdî", Script::Latin),
+    Language { aliases: &["kirghiz"], ..language("ky", "kir", "Kyrgyz", "Кыргызча", Script::Cyrillic) },
+    language("lo", "lao", "Lao", "ລາວ", Script::Lao),
+    language("la", "lat", "Latin", "Latina", Script::Latin),
+    language("lv", "lav", "Latvian", "Latviešu", Script::Latin),
+    language("lt", "lit", "Lithuanian", "Lietuvių", Script::Latin),
+    Language { aliases: &["letzeburgesch"], ..language("lb", "ltz", "Luxembourgish", "Lëtzebuergesch", Script::Latin) },
+    language("mk", "mkd", "Macedonian", "Македонски", Script::Cyrillic),
+    language("mg", "mlg", "Malagasy", "Malagasy", Script::Latin),
+    language("ms", "msa", "Malay", "Bahasa Melayu", Script::Latin),
+    language("ml", "mal", "Malayalam", "മലയാളം", Script::Malayalam),
+    language("mt", "mlt", "Maltese", "Malti", Script::Latin),
+    language("mi", "mri", "Maori", "Māori", Script::Latin),
+    language("mr", "mar", "Marathi", "मराठी", Script::Devanagari),
+    language("mn", "mon", "Mongolian", "Монгол", Script::Cyrillic),
+    language("ne", "nep", "Nepali", "नेपाली", Script::Devanagari),
+    Language {
+        aliases: &["no", "nor", "bokmal", "norwegian bokmal"],
+        provider_codes: &[("google", "no")],
+        ..language("nb", "nob", "Norwegian", "Norsk bokmål", Script::Latin)
+    },
+    Language { aliases: &["pushto"], ..language("ps", "pus", "Pashto", "پښتو", Script::Arabic) },
+    Language { aliases: &["farsi"], ..language("fa", "fas", "Persian", "فارسی", Script::Arabic) },
+    language("pl", "pol", "Polish", "Polski", Script::Latin),
+    Language {
+        aliases: &["pt-pt"],
+        provider_codes: &[("deepl", "PT-PT")],
+        ..language("pt", "por", "Portuguese", "Português", Script::Latin)
+    },
+    Language {
+        aliases: &["brazilian portuguese", "brazilian"],
+        provider_codes: &[("google", "pt"), ("deepl", "PT-BR"), ("libretranslate", "pt")],
+        ..language("pt-BR", "por", "Portuguese (Brazil)", "Português (Brasil)", Script::Latin)
+    },
+    Language { aliases: &["panjabi"], ..language("pa", "pan", "Punjabi", "ਪੰਜਾਬੀ", Script::Gurmukhi) },
+    Language { aliases: &["moldavian"], ..language("ro", "ron", "Romanian", "Română", Script::Latin) },
+    language("ru", "rus", "Russian", "Русский", Script::Cyrillic),
+    language("sm", "smo", "Samoan", "Gagana Sāmoa", Script::Latin),
+    Language { aliases: &["gaelic"], ..language("gd", "gla", "Scots Gaelic", "Gàidhlig", Script::Latin) },
+    language("sr", "srp", "Serbian", "Српски", Script::Cyrillic),
+    Language { aliases: &["sotho"], ..language("st", "sot", "Sesotho", "Sesotho", Script::Latin) },
+    language("sn", "sna", "Shona", "chiShona", Script::Latin),
+    language("sd", "snd", "Sindhi", "سنڌي", Script::Arabic),
+    Language { aliases: &["sinhalese"], ..language("si", "sin", "Sinhala", "සිංහල", Script::Sinhala) },
+    language("sk", "slk", "Slovak", "Slovenčina", Script::Latin),
+    Language { aliases: &["slovene"], ..language("sl", "slv", "Slovenian", "Slovenščina", Script::Latin) },
+    language("so", "som", "Somali", "Soomaali", Script::Latin),
+    Language { aliases: &["castilian"], ..language("es", "spa", "Spanish", "Español", Script::Latin) },
+    language("su", "sun", "Sundanese", "Basa Sunda", Script::Latin),
+    language("sw", "swa", "Swahili", "Kiswahili", Script::Latin),
+    language("sv", "swe", "Swedish", "Svenska", Script::Latin),
+    Language { aliases: &["filipino", "fil"], ..language("tl", "tgl", "Tagalog", "Tagalog", Script::Latin) },
+    language("tg", "tgk", "Tajik", "Тоҷикӣ", Script::Cyrillic),
+    language("ta", "tam", "Tamil", "தமிழ்", Script::Tamil),
+    language("tt", "tat", "Tatar", "Татар", Script::Cyrillic),
+    language("te", "tel", "Telugu", "తెలుగు", Script::Telugu),
+    language("th", "tha", "Thai", "ไทย", Script::Thai),
+    language("tr", "tur", "Turkish", "Türkçe", Script::Latin),
+    language("tk", "tuk", "Turkmen", "Türkmençe", Script::Latin),
+    language("uk", "ukr", "Ukrainian", "Українська", Script::Cyrillic),
+    language("ur", "urd", "Urdu", "اردو", Script::Arabic),
+    Language { aliases: &["uighur"], ..language("ug", "uig", "Uyghur", "ئۇيغۇرچە", Script::Arabic) },
+    language("uz", "uzb", "Uzbek", "Oʻzbek", Script::Latin),
+    language("vi", "vie", "Vietnamese", "Tiếng Việt", Script::Latin),
+    language("cy", "cym", "Welsh", "Cymraeg", Script::Latin),
+    language("xh", "xho", "Xhosa", "isiXhosa", Script::Latin),
+    language("yi", "yid", "Yiddish", "ייִדיש", Script::Hebrew),
+    language("yo", "yor", "Yoruba", "Yorùbá", Script::Latin),
+    language("zu", "zul", "Zulu", "isiZulu", Script::Latin),
+];
+
+/// Find language by code (ISO 639-1 or 639-3), English or native name, or alias; case-insensitive
+/// Regional codes without an entry of their own fall back to the base language ("de-AT" -> German)
+pub fn find(query: &str) -> Option<&'static Language> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+
+    let exact = LANGUAGES.iter().find(|language| {
+        language.code.to_lowercase() == query
+            || language.code3 == query
+            || language.name.to_lowercase() == query
+            || language.native.to_lowercase() == query
+            || language.aliases.contains(&query.as_str())
+    });
+
+    exact.or_else(|| {
+        let (base, _) = query.split_once(['-', '_'])?;
+        LANGUAGES.iter().find(|language| language.code == base)
+    })
+}
+
+/// Language code for a language setting; "auto" and unknown values are returned as-is
+pub fn to_code(language: &str) -> &str {
+    if language.trim().eq_ignore_ascii_case("auto") {
+        return "auto";
+    }
+
+    find(language).map_or(language, |language| language.code)
+}
+
+/// English name of a language code, e.g. "uk" -> "Ukrainian"; unknown codes are returned as-is
+pub fn name(code: &str) -> &str {
+    find(code).map_or(code, |language| language.name)
+}
+
+/// Code a provider expects for a language ("zh" -> "zh-CN" for Google)
+/// Unknown codes and "auto" are passed through
+pub fn provider_code(code: &str, provider: &str) -> String {
+    find(code)
+        .and_then(|language| {
+            language
+                .provider_codes
+                .iter()
+                .find(|(name, _)| *name == provider)
+                .map(|(_, provider_code)| provider_code.to_string())
+        })
+        .unwrap_or_else(|| code.to_string())
+}
+
+/// Closest language for a misspelled name, e.g. "Ukranian" -> Ukrainian
+pub fn suggest(query: &str) -> Option<&'static Language> {
+    let lower = query.trim().to_lowercase();
+    let query = lower.as_str();
+    let max_distance = (query.chars().count() / 3).max(1);
+
+    LANGUAGES
+        .iter()
+        .flat_map(|language| {
+            [language.name, language.native]
+                .into_iter()
+                .chain(language.aliases.iter().copied())
+                .map(move |name| (edit_distance(query, &name.to_lowercase()), language))
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, language)| language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        for query in ["uk", "ukr", "Ukrainian", "українська", " UKRAINIAN "] {
+            assert_eq!(find(query).map(|l| l.code), Some("uk"), "{}", query);
+        }
+
+        assert_eq!(find("Farsi").map(|l| l.code), Some("fa"));
+        assert_eq!(find("zh-CN").map(|l| l.code), Some("zh"));
+        assert_eq!(find("zh-tw").map(|l| l.code), Some("zh-TW"));
+        assert_eq!(find("de-AT").map(|l| l.code), Some("de"));
+        assert!(find("Klingon").is_none());
+
+        assert_eq!(to_code("Auto"), "auto");
+        assert_eq!(to_code("German"), "de");
+        assert_eq!(name("iw"), "Hebrew");
+        assert_eq!(name("xx"), "xx");
+    }
+
+    #[test]
+    fn test_registry_is_sorted_by_name() {
+        for pair in LANGUAGES.windows(2) {
+            assert!(
+                pair[0].name < pair[1].name,
+                "{} / {}",
+                pair[0].name,
+                pair[1].name
+            );
+        }
+        assert_eq!(find("zh").map(|l| l.code), Some("zh"));
+        assert_eq!(find("he").map(|l| l.code), Some("he"));
+    }
+
+    #[test]
+    fn test_provider_code() {
+        assert_eq!(provider_code("zh", "google"), "zh-CN");
+        assert_eq!(provider_code("zh", "libretranslate"), "zh");
+        assert_eq!(provider_code("zh-TW", "libretranslate"), "zt");
+        assert_eq!(provider_code("pt-BR", "deepl"), "PT-BR");
+        assert_eq!(provider_code("ru", "google"), "ru");
+        assert_eq!(provider_code("auto", "google"), "auto");
+    }
+
+    #[test]
+    fn test_suggest() {
+        let suggest_name = |query| suggest(query).map(|l| l.name);
+        assert_eq!(suggest_name("Ukranian"), Some("Ukrainian"));
+        assert_eq!(suggest_name("germn"), Some("German"));
+        assert_eq!(suggest_name("Portugese"), Some("Portuguese"));
+        assert_eq!(suggest_name("xyz"), None);
+    }
+}
@@ -1,41 +1,95 @@
 mod cli;
 mod clipboard;
 mod config;
+mod detect;
+mod document;
+mod glossary;
+mod history;
 mod interactive;
 mod keyboard;
+mod languages;
+mod markdown;
+mod network;
+mod output;
+mod part_of_speech;
+mod protect;
 mod providers;
+mod quiz;
+mod resources;
 mod speech;
+mod subtitles;
 mod translator;
+mod vocabulary;
 mod window;
 
-use cli::CliHandler;
+use cli::{CliArgs, CliHandler};
 use interactive::InteractiveMode;
 use keyboard::KeyboardHook;
+use output::OutputFormat;
+use providers::cache::TranslationCache;
 use std::env;
 use translator::Translator;
-use windows::Win32::System::Console::SetConsoleCtrlHandler;
+use windows::Win32::Foundation::{BOOL, FALSE};
+use windows::Win32::System::Console::{
+    SetConsoleCtrlHandler, CTRL_CLOSE_EVENT, CTRL_LOGOFF_EVENT, CTRL_SHUTDOWN_EVENT,
+};
+
+// Закрытие окна консоли, выход из системы и выключение завершают процесс без Drop,
+// поэтому кэш переводов сохраняется здесь
+unsafe extern "system" fn console_handler(ctrl_type: u32) -> BOOL {
+    if matches!(
+        ctrl_type,
+        CTRL_CLOSE_EVENT | CTRL_LOGOFF_EVENT | CTRL_SHUTDOWN_EVENT
+    ) {
+        TranslationCache::flush_all();
+    }
+    // Передаем событие дальше, процесс завершается как обычно
+    FALSE
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Отключаем стандартную обработку Ctrl+C в консоли Windows
     unsafe {
         SetConsoleCtrlHandler(None, true)?;
+        SetConsoleCtrlHandler(Some(console_handler), true)?;
     }
 
     // Получаем аргументы командной строки
     let args: Vec<String> = env::args().collect();
 
-    // Если есть аргументы, работаем в режиме CLI
-    if args.len() > 1 {
-        let cli_handler = match CliHandler::new() {
+    // Если есть аргументы или текст подан через pipe, работаем в режиме CLI
+    // Запуск без stdin (планировщик, ярлык, служба) открывает объединенный режим
+    if args.len() > 1 || cli::stdin_is_piped() {
+        // Формат вывода нужен раньше разбора, чтобы ошибки для скриптов тоже были в JSON
+        let json_output = CliArgs::requested_format(&args[1..]) == OutputFormat::Json;
+
+        let cli_args = match CliArgs::parse(&args[1..]) {
+            Ok(cli_args) => cli_args,
+            Err(e) => {
+                if json_output {
+                    println!("{}", output::error_json(&e.to_string()));
+                } else {
+                    eprintln!("Error: {}", e);
+                    eprintln!("Use --help for usage information");
+                }
+                return Err(e);
+            }
+        };
+
+        let cli_handler = match CliHandler::new(&cli_args) {
             Ok(handler) => handler,
             Err(e) => {
-                println!("Failed to initialize CLI handler: {}", e);
+                if json_output {
+                    println!("{}", output::error_json(&e.to_string()));
+                } else {
+                    println!("Failed to initialize CLI handler: {}", e);
+                }
                 return Err(e);
             }
         };
 
-        return cli_handler.process_args(args).await;
+        return cli_handler.process_args(cli_args).await;
     }
 
     // Если аргументов нет, запускаем объединенный GUI+Interactive режим
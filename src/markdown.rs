@@ -0,0 +1,348 @@
+use crate::document::{push_trimmed, push_verbatim, Segment};
+use crate::protect;
+
+/// Split Markdown into prose to translate and markup kept verbatim:
+/// front matter, code blocks, HTML tag lines, tables and heading/list/quote markers
+/// Every paragraph, heading or list item is one segment; inline code and link URLs
+/// stay in it and are masked by protect, so links are translated in context
+pub fn split(content: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut paragraph = String::new(); // Consecutive plain prose lines
+    let mut lines = content.split_inclusive('\n').peekable();
+    let mut previous_blank = true;
+    let mut in_indented_code = false;
+
+    // YAML/TOML front matter
+    if let Some(delimiter) = lines.peek().map(|line| line.trim_end()) {
+        if delimiter == "---" || delimiter == "+++" {
+            let delimiter = delimiter.to_string();
+            push_verbatim(&mut segments, lines.next().unwrap_or_default());
+            for line in lines.by_ref() {
+                push_verbatim(&mut segments, line);
+                if line.trim_end() == delimiter || line.trim_end() == "..." {
+                    break;
+                }
+            }
+        }
+    }
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        // Fenced code block
+        if let Some(fence) = fence_marker(trimmed) {
+            flush_paragraph(&mut segments, &mut paragraph);
+            push_verbatim(&mut segments, line);
+            for line in lines.by_ref() {
+                push_verbatim(&mut segments, line);
+                if is_closing_fence(line.trim(), fence) {
+                    break;
+                }
+            }
+            previous_blank = false;
+            in_indented_code = false;
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            flush_paragraph(&mut segments, &mut paragraph);
+            push_verbatim(&mut segments, line);
+            previous_blank = true;
+            continue;
+        }
+
+        // Indented code block: starts after a blank line
+        let is_indented = line.starts_with("    ") || line.starts_with('\t');
+        if is_indented
+            && (previous_blank || in_indented_code)
+            && paragraph.is_empty()
+            && strip_list_marker(trimmed).is_none()
+        {
+            push_verbatim(&mut segments, line);
+            previous_blank = false;
+            in_indented_code = true;
+            continue;
+        }
+
+        previous_blank = false;
+        in_indented_code = false;
+
+        // HTML comment, possibly spanning several lines
+        if trimmed.starts_with("<!--") {
+            flush_paragraph(&mut segments, &mut paragraph);
+            push_verbatim(&mut segments, line);
+            if !trimmed.contains("-->") {
+                for line in lines.by_ref() {
+                    push_verbatim(&mut segments, line);
+                    if line.contains("-->") {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+
+        // Lines that contain only markup
+        if is_tags_only(trimmed)
+            || is_rule(trimmed)
+            || is_link_definition(trimmed)
+            || is_table_separator(trimmed)
+        {
+            flush_paragraph(&mut segments, &mut paragraph);
+            push_verbatim(&mut segments, line);
+            continue;
+        }
+
+        if trimmed.starts_with('|') {
+            flush_paragraph(&mut segments, &mut paragraph);
+            push_table_row(&mut segments, line);
+            continue;
+        }
+
+        let prefix_length = block_prefix_length(line);
+        if prefix_length > 0 {
+            flush_paragraph(&mut segments, &mut paragraph);
+            push_verbatim(&mut segments, &line[..prefix_length]);
+            push_prose(&mut segments, &line[prefix_length..]);
+        } else {
+            paragraph.push_str(line);
+        }
+    }
+
+    flush_paragraph(&mut segments, &mut paragraph);
+    segments
+}
+
+fn flush_paragraph(segments: &mut Vec<Segment>, paragraph: &mut String) {
+    if !paragraph.is_empty() {
+        push_prose(segments, paragraph);
+        paragraph.clear();
+    }
+}
+
+/// Text without letters outside inline markup (numbers, a lone code span or URL) is not translated
+fn push_prose(segments: &mut Vec<Segment>, text: &str) {
+    if protect::protect(text).text.chars().any(char::is_alphabetic) {
+        push_trimmed(segments, text);
+    } else {
+        push_verbatim(segments, text);
+    }
+}
+
+/// Table row: pipes are kept, cells are translated
+fn push_table_row(segments: &mut Vec<Segment>, line: &str) {
+    for (index, cell) in line.split('|').enumerate() {
+        if index > 0 {
+            push_verbatim(segments, "|");
+        }
+        push_prose(segments, cell);
+    }
+}
+
+/// "```" or "~~~" (three or more) opening a code block
+fn fence_marker(trimmed: &str) -> Option<&str> {
+    ["```", "~~~"].into_iter().find_map(|marker| {
+        let ch = marker.chars().next()?;
+        trimmed.starts_with(marker).then(|| {
+            let length = trimmed.chars().take_while(|&c| c == ch).count();
+            &trimmed[..length]
+        })
+    })
+}
+
+/// Closing fence uses the same character and is at least as long as the opening one
+fn is_closing_fence(trimmed: &str, fence: &str) -> bool {
+    let ch = fence.chars().next().unwrap_or('`');
+    trimmed.len() >= fence.len() && trimmed.chars().all(|c| c == ch)
+}
+
+/// Horizontal rule ("---", "* * *") or setext heading underline ("===")
+fn is_rule(trimmed: &str) -> bool {
+    let marks: String = trimmed.chars().filter(|c| !c.is_whitespace()).collect();
+    marks.len() >= 3
+        && ['-', '*', '_', '=']
+            .iter()
+            .any(|&ch| marks.chars().all(|c| c == ch))
+}
+
+/// Line of HTML tags without text: `<p align="center">`, `<img src="logo.png"><br>`
+fn is_tags_only(trimmed: &str) -> bool {
+    let mut rest = trimmed;
+    while !rest.is_empty() {
+        match rest.starts_with('<').then(|| tag_length(rest)).flatten() {
+            Some(length) => rest = rest[length..].trim_start(),
+            None => return false,
+        }
+    }
+    true
+}
+
+/// "[id]: https://example.com"
+fn is_link_definition(trimmed: &str) -> bool {
+    trimmed.starts_with('[') && trimmed.contains("]:")
+}
+
+/// "|---|:---:|"
+fn is_table_separator(trimmed: &str) -> bool {
+    trimmed.contains('-') && trimmed.chars().all(|c| "|-: ".contains(c))
+}
+
+/// Length of heading, list, task and quote markers at line start (with indentation)
+fn block_prefix_length(line: &str) -> usize {
+    let mut rest = line;
+    let mut found = false;
+
+    loop {
+        let trimmed = rest.trim_start();
+        let stripped = trimmed
+            .strip_prefix('>')
+            .or_else(|| strip_heading(trimmed))
+            .or_else(|| strip_list_marker(trimmed));
+
+        match stripped {
+            Some(stripped) => {
+                rest = stripped;
+                found = true;
+            }
+            None => break,
+        }
+    }
+
+    if !found {
+        return 0;
+    }
+
+    let trimmed = rest.trim_start();
+    let rest = ["[ ] ", "[x] ", "[X] "]
+        .iter()
+        .find_map(|checkbox| trimmed.strip_prefix(checkbox))
+        .unwrap_or(trimmed);
+
+    line.len() - rest.trim_start().len()
+}
+
+/// "# ".."###### "
+fn strip_heading(trimmed: &str) -> Option<&str> {
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    (rest.starts_with(' ') || rest.trim().is_empty()).then_some(rest)
+}
+
+/// "- ", "* ", "+ ", "1. ", "1) "
+fn strip_list_marker(trimmed: &str) -> Option<&str> {
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    let marker_length = if digits > 0 {
+        match trimmed[digits..].chars().next() {
+            Some('.') | Some(')') if digits <= 9 => digits + 1,
+            _ => return None,
+        }
+    } else if trimmed.starts_with(['-', '*', '+']) {
+        1
+    } else {
+        return None;
+    };
+
+    let rest = &trimmed[marker_length..];
+    (rest.starts_with(' ') || rest.starts_with('\t')).then_some(rest)
+}
+
+/// "<https://example.com>", "<br>", "</kbd>"
+fn tag_length(text: &str) -> Option<usize> {
+    let second = text[1..].chars().next()?;
+    if !(second.is_ascii_alphabetic() || second == '/' || second == '!') {
+        return None;
+    }
+    let close = text.find('>')?;
+    (!text[..close].contains('\n')).then_some(close + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::join;
+
+    fn texts(segments: &[Segment]) -> Vec<&str> {
+        segments
+            .iter()
+            .filter_map(|segment| match segment {
+                Segment::Text(text) => Some(text.as_str()),
+                Segment::Verbatim(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_markup_is_kept() {
+        let content = "---\ntitle: Guide\n---\n\
+# Quick Start\n\
+\n\
+Run `tagent hello` to translate\na word.\n\
+\n\
+```bash\ntagent --help\n```\n\
+\n\
+- See the [user guide](https://example.com/guide.md) first\n\
+2. [x] Done\n\
+> Quoted text\n\
+\n    indented code\n\
+\n\
+| Option | Meaning |\n|---|---|\n| `-h` | Show help |\n\
+<p align=\"center\"><img src=\"logo.png\"></p>\n\
+\n\
+<p>Press <kbd>Ctrl</kbd> twice</p>\n\
+Visit https://example.com.\n";
+
+        let segments = split(content);
+        assert_eq!(join(&segments, &[]), content);
+        assert_eq!(
+            texts(&segments),
+            vec![
+                "Quick Start",
+                "Run `tagent hello` to translate\na word.",
+                "See the [user guide](https://example.com/guide.md) first",
+                "Done",
+                "Quoted text",
+                "Option",
+                "Meaning",
+                "Show help",
+                "<p>Press <kbd>Ctrl</kbd> twice</p>\nVisit https://example.com.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_translations_are_placed_into_markup() {
+        let segments = split(
+            "## Links\n\n![Logo](logo.png) and [docs][1] for `tagent -h`.\n\n[1]: https://a.b\n",
+        );
+        // Provider sees the whole sentence with markup masked, as the translator sends it
+        let translated: Vec<String> = texts(&segments)
+            .iter()
+            .map(|text| {
+                let protected = protect::protect(text);
+                protected.restore(&protected.text.to_uppercase()).0
+            })
+            .collect();
+
+        assert_eq!(
+            join(&segments, &translated),
+            "## LINKS\n\n![LOGO](logo.png) AND [DOCS][1] FOR `tagent -h`.\n\n[1]: https://a.b\n"
+        );
+    }
+
+    #[test]
+    fn test_block_markers() {
+        assert_eq!(block_prefix_length("### Title\n"), 4);
+        assert_eq!(block_prefix_length("  > - [ ] task\n"), 10);
+        assert_eq!(block_prefix_length("10. item\n"), 4);
+        assert_eq!(block_prefix_length("*emphasis* text\n"), 0);
+        assert_eq!(block_prefix_length("#hashtag\n"), 0);
+        assert!(is_rule("* * *"));
+        assert!(!is_rule("--"));
+        assert!(is_tags_only("<p align=\"center\"> <br/>"));
+        assert!(!is_tags_only("<p>Intro</p>"));
+        assert!(!is_tags_only("<3 Markdown>"));
+    }
+}
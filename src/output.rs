@@ -0,0 +1,209 @@
+use crate::glossary::GlossaryHit;
+use crate::providers::DictionaryEntry;
+use serde::Serialize;
+use serde_json::json;
+use std::error::Error;
+
+/// CLI output format
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Text, // Human-readable output (default)
+    Json, // One JSON object per invocation (array for several target languages)
+    Tsv,  // One tab-separated line per invocation
+}
+
+impl OutputFormat {
+    pub fn parse(value: &str) -> Result<Self, Box<dyn Error>> {
+        match value.trim().to_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "tsv" => Ok(OutputFormat::Tsv),
+            _ => Err(format!(
+                "Unknown output format: {} (expected json, text or tsv)",
+                value
+            )
+            .into()),
+        }
+    }
+}
+
+/// Translation result for machine-readable output
+#[derive(Debug, Serialize)]
+pub struct TranslationOutput {
+    pub source_text: String,
+    pub source_lang: String, // Requested source language ("auto" for detection)
+    pub detected_source_lang: Option<String>,
+    pub target_lang: String,
+    pub provider: String,
+    pub translation: String,
+    pub dictionary: Option<DictionaryEntry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>, // Placeholders or tags damaged by the provider
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub glossary: Vec<GlossaryHit>, // Glossary terms applied to the translation
+}
+
+impl TranslationOutput {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|e| error_json(&e.to_string()))
+    }
+
+    /// Columns: source text, source language, target language, provider,
+    /// translation, dictionary ("pos: def1, def2; pos: ...")
+    pub fn to_tsv(&self) -> String {
+        let source_lang = self
+            .detected_source_lang
+            .as_deref()
+            .unwrap_or(&self.source_lang);
+
+        let dictionary = self
+            .dictionary
+            .as_ref()
+            .map(|entry| {
+                entry
+                    .definitions
+                    .iter()
+                    .map(|pos_entry| {
+                        let definitions = pos_entry
+                            .definitions
+                            .iter()
+                            .map(|def| def.text.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        format!("{}: {}", pos_entry.part_of_speech, definitions)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            })
+            .unwrap_or_default();
+
+        [
+            self.source_text.as_str(),
+            source_lang,
+            self.target_lang.as_str(),
+            self.provider.as_str(),
+            self.translation.as_str(),
+            dictionary.as_str(),
+        ]
+        .iter()
+        .map(|field| escape_tsv(field))
+        .collect::<Vec<_>>()
+        .join("\t")
+    }
+}
+
+/// Results for several target languages as one JSON array
+pub fn list_to_json(outputs: &[TranslationOutput]) -> String {
+    serde_json::to_string_pretty(outputs).unwrap_or_else(|e| error_json(&e.to_string()))
+}
+
+/// Error in JSON output mode: {"error": "..."}
+pub fn error_json(message: &str) -> String {
+    serde_json::to_string_pretty(&json!({ "error": message }))
+        .unwrap_or_else(|_| r#"{"error": "Unknown error"}"#.to_string())
+}
+
+/// Keep one record per line: escape backslashes, tabs and line breaks
+fn escape_tsv(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{Definition, Frequency, PartOfSpeechEntry};
+    use serde_json::Value;
+
+    fn output() -> TranslationOutput {
+        TranslationOutput {
+            source_text: "cat".to_string(),
+            source_lang: "auto".to_string(),
+            detected_source_lang: Some("en".to_string()),
+            target_lang: "ru".to_string(),
+            provider: "Google Translate".to_string(),
+            translation: "кот".to_string(),
+            dictionary: Some(DictionaryEntry {
+                word: "cat".to_string(),
+                definitions: vec![PartOfSpeechEntry {
+                    part_of_speech: "noun".to_string(),
+                    definitions: vec![
+                        Definition {
+                            text: "кот".to_string(),
+                            synonyms: vec!["cat".to_string(), "tomcat".to_string()],
+                            frequency: Some(Frequency::Common),
+                        },
+                        Definition {
+                            text: "кошка".to_string(),
+                            synonyms: Vec::new(),
+                            frequency: None,
+                        },
+                    ],
+                }],
+                provider: "Google Translate".to_string(),
+                transliteration: Some("kat".to_string()),
+                examples: vec!["the cat sat on the mat".to_string()],
+                ..DictionaryEntry::default()
+            }),
+            warnings: Vec::new(),
+            glossary: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_json_output() {
+        let json: Value = serde_json::from_str(&output().to_json()).unwrap();
+        assert_eq!(json["source_text"], "cat");
+        assert_eq!(json["detected_source_lang"], "en");
+        assert!(json.get("warnings").is_none());
+        assert!(json.get("glossary").is_none());
+        assert_eq!(json["translation"], "кот");
+        assert_eq!(
+            json["dictionary"]["definitions"][0]["definitions"][0]["synonyms"][1],
+            "tomcat"
+        );
+        let dictionary = &json["dictionary"];
+        assert_eq!(
+            dictionary["definitions"][0]["definitions"][0]["frequency"],
+            "common"
+        );
+        assert!(dictionary["definitions"][0]["definitions"][1]
+            .get("frequency")
+            .is_none());
+        assert_eq!(dictionary["transliteration"], "kat");
+        assert_eq!(dictionary["examples"][0], "the cat sat on the mat");
+        assert!(dictionary.get("source_definitions").is_none());
+
+        let mut german = output();
+        german.target_lang = "de".to_string();
+        let list: Value = serde_json::from_str(&list_to_json(&[output(), german])).unwrap();
+        assert_eq!(list[1]["target_lang"], "de");
+
+        let error: Value = serde_json::from_str(&error_json("HTTP error: 429")).unwrap();
+        assert_eq!(error["error"], "HTTP error: 429");
+    }
+
+    #[test]
+    fn test_tsv_output() {
+        assert_eq!(
+            output().to_tsv(),
+            "cat\ten\tru\tGoogle Translate\tкот\tnoun: кот, кошка"
+        );
+
+        let mut multi_line = output();
+        multi_line.translation = "строка\tодин\nстрока два".to_string();
+        multi_line.dictionary = None;
+        assert!(multi_line
+            .to_tsv()
+            .ends_with("строка\\tодин\\nстрока два\t"));
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(OutputFormat::parse("JSON").unwrap(), OutputFormat::Json);
+        assert!(OutputFormat::parse("xml").is_err());
+    }
+}
@@ -0,0 +1,243 @@
+use crate::languages;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Labels shipped with the program; a user file with the same format overrides them
+const EMBEDDED_LABELS: &str = include_str!("../assets/part_of_speech.ini");
+
+/// Canonical part of speech of a dictionary entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartOfSpeech {
+    Noun,
+    Verb,
+    Adjective,
+    Adverb,
+    Preposition,
+    Conjunction,
+    Pronoun,
+    Interjection,
+    Article,
+    Determiner,
+    Participle,
+    Numeral,
+    Particle,
+    Abbreviation,
+    Phrase,
+    Other,
+}
+
+impl PartOfSpeech {
+    pub const ALL: [PartOfSpeech; 16] = [
+        PartOfSpeech::Noun,
+        PartOfSpeech::Verb,
+        PartOfSpeech::Adjective,
+        PartOfSpeech::Adverb,
+        PartOfSpeech::Preposition,
+        PartOfSpeech::Conjunction,
+        PartOfSpeech::Pronoun,
+        PartOfSpeech::Interjection,
+        PartOfSpeech::Article,
+        PartOfSpeech::Determiner,
+        PartOfSpeech::Participle,
+        PartOfSpeech::Numeral,
+        PartOfSpeech::Particle,
+        PartOfSpeech::Abbreviation,
+        PartOfSpeech::Phrase,
+        PartOfSpeech::Other,
+    ];
+
+    /// Key used in label files ("noun")
+    pub fn key(self) -> &'static str {
+        match self {
+            PartOfSpeech::Noun => "noun",
+            PartOfSpeech::Verb => "verb",
+            PartOfSpeech::Adjective => "adjective",
+            PartOfSpeech::Adverb => "adverb",
+            PartOfSpeech::Preposition => "preposition",
+            PartOfSpeech::Conjunction => "conjunction",
+            PartOfSpeech::Pronoun => "pronoun",
+            PartOfSpeech::Interjection => "interjection",
+            PartOfSpeech::Article => "article",
+            PartOfSpeech::Determiner => "determiner",
+            PartOfSpeech::Participle => "participle",
+            PartOfSpeech::Numeral => "numeral",
+            PartOfSpeech::Particle => "particle",
+            PartOfSpeech::Abbreviation => "abbreviation",
+            PartOfSpeech::Phrase => "phrase",
+            PartOfSpeech::Other => "other",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|pos| pos.key() == key)
+    }
+}
+
+/// Part of speech labels for every target language
+#[derive(Debug, Default)]
+pub struct PartOfSpeechLabels {
+    labels: HashMap<String, HashMap<PartOfSpeech, String>>, // Language code -> labels
+    aliases: HashMap<String, PartOfSpeech>,                 // Other names, lowercase
+}
+
+impl PartOfSpeechLabels {
+    /// INI sections per language code or name: "[ru]" with "noun = Существительное"
+    /// "[aliases]" maps other names to keys: "exclamation = interjection"
+    pub fn parse(content: &str) -> Self {
+        let mut result = Self::default();
+        let mut section: Option<String> = None;
+
+        for line in content.lines() {
+            let line = line.trim().trim_start_matches('\u{feff}');
+            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let name = name.trim();
+                section = Some(if name.eq_ignore_ascii_case("aliases") {
+                    "aliases".to_string()
+                } else {
+                    languages::to_code(name).to_string()
+                });
+                continue;
+            }
+
+            let (Some(section), Some((key, value))) = (&section, line.split_once('=')) else {
+                continue;
+            };
+            let (key, value) = (key.trim().to_lowercase(), value.trim());
+
+            if section == "aliases" {
+                if let Some(pos) = PartOfSpeech::from_key(&value.to_lowercase()) {
+                    result.aliases.insert(key, pos);
+                }
+            } else if let Some(pos) = PartOfSpeech::from_key(&key) {
+                if !value.is_empty() {
+                    result
+                        .labels
+                        .entry(section.clone())
+                        .or_default()
+                        .insert(pos, value.to_string());
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Labels shipped with the program
+    pub fn embedded() -> Self {
+        Self::parse(EMBEDDED_LABELS)
+    }
+
+    /// Embedded labels with the user file on top (if it exists)
+    pub fn load(user_file: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut labels = Self::embedded();
+
+        if user_file.is_file() {
+            let content = fs::read_to_string(user_file)
+                .map_err(|e| format!("Failed to read labels {}: {}", user_file.display(), e))?;
+            labels.merge(Self::parse(&content));
+        }
+
+        Ok(labels)
+    }
+
+    /// Labels of other replace labels with the same language and key
+    pub fn merge(&mut self, other: Self) {
+        for (language, labels) in other.labels {
+            self.labels.entry(language).or_default().extend(labels);
+        }
+        self.aliases.extend(other.aliases);
+    }
+
+    /// Label in the language (code), e.g. "Существительное" for a noun in "ru"
+    /// Regional variants fall back to the base language, missing labels to English
+    pub fn label(&self, pos: PartOfSpeech, language: &str) -> &str {
+        let base = language.split('-').next().unwrap_or(language);
+
+        [language, base, "en"]
+            .into_iter()
+            .find_map(|code| self.labels.get(code)?.get(&pos))
+            .map_or(pos.key(), |label| label.as_str())
+    }
+
+    /// Canonical part of speech for a provider string in any language ("noun", "Глагол", "名詞")
+    pub fn normalize(&self, text: &str) -> PartOfSpeech {
+        let text = text.trim().to_lowercase();
+
+        PartOfSpeech::from_key(&text)
+            .or_else(|| self.aliases.get(&text).copied())
+            .or_else(|| {
+                self.labels.values().find_map(|labels| {
+                    labels
+                        .iter()
+                        .find(|(_, label)| label.to_lowercase() == text)
+                        .map(|(pos, _)| *pos)
+                })
+            })
+            .unwrap_or(PartOfSpeech::Other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_labels() {
+        let labels = PartOfSpeechLabels::embedded();
+
+        assert_eq!(labels.label(PartOfSpeech::Noun, "ru"), "Существительное");
+        assert_eq!(labels.label(PartOfSpeech::Verb, "ja"), "動詞");
+        assert_eq!(labels.label(PartOfSpeech::Adjective, "pt-BR"), "Adjetivo");
+        assert_eq!(labels.label(PartOfSpeech::Adverb, "sw"), "Adverb");
+
+        for code in ["ru", "uk", "es", "fr", "de", "it", "pt", "nl", "pl", "tr"] {
+            assert!(labels.labels.contains_key(code), "{}", code);
+        }
+        for code in ["zh", "ja", "ko", "ar", "hi", "he", "fa", "el"] {
+            assert!(labels.labels.contains_key(code), "{}", code);
+        }
+
+        // Every language has every label, and each label reads back as its part of speech
+        for (language, language_labels) in &labels.labels {
+            for pos in PartOfSpeech::ALL {
+                let label = language_labels.get(&pos);
+                assert!(label.is_some(), "{} has no {}", language, pos.key());
+                assert_eq!(labels.normalize(label.unwrap()), pos, "{}", label.unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        let labels = PartOfSpeechLabels::embedded();
+
+        assert_eq!(labels.normalize("noun"), PartOfSpeech::Noun);
+        assert_eq!(
+            labels.normalize(" Exclamation "),
+            PartOfSpeech::Interjection
+        );
+        assert_eq!(labels.normalize("глагол"), PartOfSpeech::Verb);
+        assert_eq!(labels.normalize("Präposition"), PartOfSpeech::Preposition);
+        assert_eq!(labels.normalize("something else"), PartOfSpeech::Other);
+    }
+
+    #[test]
+    fn test_user_labels_override_embedded() {
+        let mut labels = PartOfSpeechLabels::embedded();
+        labels.merge(PartOfSpeechLabels::parse(
+            "[Russian]\nnoun = Сущ.\n\n[sw]\nnoun = Nomino\n\n[aliases]\nnoun phrase = noun\n",
+        ));
+
+        assert_eq!(labels.label(PartOfSpeech::Noun, "ru"), "Сущ.");
+        assert_eq!(labels.label(PartOfSpeech::Verb, "ru"), "Глагол");
+        assert_eq!(labels.label(PartOfSpeech::Noun, "sw"), "Nomino");
+        assert_eq!(labels.label(PartOfSpeech::Verb, "sw"), "Verb");
+        assert_eq!(labels.normalize("Noun phrase"), PartOfSpeech::Noun);
+    }
+}
@@ -0,0 +1,390 @@
+use crate::glossary::{Glossary, GlossaryHit};
+
+/// Text with placeholder tokens replaced by numbered markers that translation keeps intact
+/// Protected: {name}, {{name}}, {0}, {}, %s, %d, %1$s, %(name)s, %.2f, %%,
+/// HTML/XML tags, URLs, email addresses, Markdown code spans and link syntax and glossary terms
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtectedText {
+    pub text: String,    // Text sent to the translation provider
+    tokens: Vec<String>, // Original tokens or glossary translations, index = marker number
+}
+
+impl ProtectedText {
+    pub fn has_tokens(&self) -> bool {
+        !self.tokens.is_empty()
+    }
+
+    /// Put original tokens back into translated text
+    /// Also returns problems: markers the provider dropped or duplicated
+    pub fn restore(&self, translated: &str) -> (String, Vec<String>) {
+        let mut result = String::with_capacity(translated.len());
+        let mut counts = vec![0usize; self.tokens.len()];
+        let mut rest = translated;
+
+        while let Some(start) = rest.find(MARKER_OPEN) {
+            result.push_str(&rest[..start]);
+            let after_open = &rest[start + MARKER_OPEN.len_utf8()..];
+
+            match parse_marker(after_open) {
+                Some((index, length)) if index < self.tokens.len() => {
+                    result.push_str(&self.tokens[index]);
+                    counts[index] += 1;
+                    rest = &after_open[length..];
+                }
+                _ => {
+                    result.push(MARKER_OPEN);
+                    rest = after_open;
+                }
+            }
+        }
+        result.push_str(rest);
+
+        let problems = self
+            .tokens
+            .iter()
+            .zip(counts)
+            .filter_map(|(token, count)| match count {
+                1 => None,
+                0 => Some(format!("{} was dropped by the provider", token)),
+                _ => Some(format!("{} was duplicated by the provider", token)),
+            })
+            .collect();
+
+        (result, problems)
+    }
+}
+
+const MARKER_OPEN: char = '⟦';
+const MARKER_CLOSE: char = '⟧';
+
+/// Replace placeholder tokens with markers ⟦0⟧, ⟦1⟧, ...
+pub fn protect(text: &str) -> ProtectedText {
+    protect_with_glossary(text, &Glossary::default()).0
+}
+
+/// Also mask glossary terms; their markers are restored as the mandated translation
+/// or the term itself (do-not-translate). Returns the terms found
+pub fn protect_with_glossary(text: &str, glossary: &Glossary) -> (ProtectedText, Vec<GlossaryHit>) {
+    let mut result = String::with_capacity(text.len());
+    let mut tokens = Vec::new();
+    let mut hits: Vec<GlossaryHit> = Vec::new();
+    let mut i = 0;
+
+    while i < text.len() {
+        let rest = &text[i..];
+        // URLs, emails and glossary terms start at a word boundary only
+        let word_start = !text[..i]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || matches!(c, '.' | '_' | '%' | '+' | '-' | '@'));
+
+        let token = match token_length(rest, word_start) {
+            Some(length) => Some((length, rest[..length].to_string())),
+            None if word_start && !glossary.is_empty() => {
+                glossary.match_at(rest).map(|(length, translation)| {
+                    let hit = GlossaryHit {
+                        term: rest[..length].to_string(),
+                        translation: translation.clone(),
+                    };
+                    if !hits.contains(&hit) {
+                        hits.push(hit);
+                    }
+                    (length, translation)
+                })
+            }
+            None => None,
+        };
+
+        match token {
+            Some((length, token)) => {
+                result.push_str(&format!("{}{}{}", MARKER_OPEN, tokens.len(), MARKER_CLOSE));
+                tokens.push(token);
+                i += length;
+            }
+            None => {
+                let ch = rest.chars().next().unwrap_or_default();
+                result.push(ch);
+                i += ch.len_utf8();
+            }
+        }
+    }
+
+    let protected = ProtectedText {
+        text: result,
+        tokens,
+    };
+    (protected, hits)
+}
+
+/// Marker number and length up to the closing bracket; providers sometimes add spaces ("⟦ 0 ⟧")
+fn parse_marker(text: &str) -> Option<(usize, usize)> {
+    let close = text.find(MARKER_CLOSE)?;
+    let index = text[..close].trim().parse().ok()?;
+    Some((index, close + MARKER_CLOSE.len_utf8()))
+}
+
+/// Length of a placeholder token at the start of text
+fn token_length(text: &str, word_start: bool) -> Option<usize> {
+    match text.chars().next()? {
+        '{' => brace_token_length(text),
+        '%' => printf_token_length(text),
+        '<' => tag_length(text),
+        '`' => code_span_length(text),
+        '[' | '!' | ']' => link_markup_length(text),
+        c if word_start && c.is_ascii_alphanumeric() => {
+            url_length(text).or_else(|| email_length(text))
+        }
+        _ => None,
+    }
+}
+
+/// <b>, </b>, <br/>, <a href="...">, <ns:tag> and <!-- comments -->
+fn tag_length(text: &str) -> Option<usize> {
+    if let Some(comment) = text.strip_prefix("<!--") {
+        return Some(4 + comment.find("-->")? + 3);
+    }
+
+    let name = text[1..].strip_prefix('/').unwrap_or(&text[1..]);
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None; // "a < b", "<3"
+    }
+
+    let end = text[1..].find(['<', '>', '\n'])? + 1;
+    (text.as_bytes()[end] == b'>').then_some(end + 1)
+}
+
+/// Markdown code span closed by the same number of backticks
+fn code_span_length(text: &str) -> Option<usize> {
+    let ticks = text.chars().take_while(|&c| c == '`').count();
+    let close = text[ticks..].find(&text[..ticks])?;
+    Some(ticks + close + ticks)
+}
+
+/// Markdown link syntax around the label, which stays translatable:
+/// "[" or "![" opening a link, "](url)" or "][ref]" closing it
+fn link_markup_length(text: &str) -> Option<usize> {
+    if let Some(after) = text.strip_prefix(']') {
+        let close = match after.chars().next()? {
+            '(' => ')',
+            '[' => ']',
+            _ => return None,
+        };
+        let end = after.find(close)?;
+        return (!after[..end].contains('\n')).then_some(end + 2);
+    }
+
+    let open = if text.starts_with("![") {
+        2
+    } else if text.starts_with('[') {
+        1
+    } else {
+        return None;
+    };
+
+    // Opening bracket only counts when the link is complete: "[label](url)"
+    let close = open + text[open..].find(']')?;
+    link_markup_length(&text[close..]).map(|_| open)
+}
+
+/// http://, https://, ftp:// and www. links without trailing punctuation
+fn url_length(text: &str) -> Option<usize> {
+    let prefix = ["http://", "https://", "ftp://", "www."]
+        .into_iter()
+        .find(|prefix| text.starts_with(prefix))?;
+
+    let end = text
+        .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"' | MARKER_OPEN))
+        .unwrap_or(text.len());
+    let url = text[..end].trim_end_matches(['.', ',', ';', ':', '!', '?', '\'']);
+    // Closing parenthesis belongs to the URL only if it was opened inside it
+    let url = match url.strip_suffix(')') {
+        Some(stripped) if !stripped.contains('(') => stripped,
+        _ => url,
+    };
+
+    (url.len() > prefix.len()).then_some(url.len())
+}
+
+/// name@example.com
+fn email_length(text: &str) -> Option<usize> {
+    let is_local = |c: char| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '%' | '+' | '-');
+    let is_domain = |c: char| c.is_ascii_alphanumeric() || matches!(c, '.' | '-');
+
+    let at = text.find(|c: char| !is_local(c))?;
+    let domain = text[at..].strip_prefix('@')?;
+    let domain_end = domain.find(|c: char| !is_domain(c)).unwrap_or(domain.len());
+    let domain = domain[..domain_end].trim_end_matches(['.', '-']);
+
+    let (_, tld) = domain.rsplit_once('.')?;
+    let valid = at > 0 && tld.len() >= 2 && tld.chars().all(|c| c.is_ascii_alphabetic());
+    valid.then_some(at + 1 + domain.len())
+}
+
+/// {name}, {0}, {}, {user.name}, {count:02} and {{name}}
+fn brace_token_length(text: &str) -> Option<usize> {
+    let (open, close) = if text.starts_with("{{") {
+        ("{{", "}}")
+    } else {
+        ("{", "}")
+    };
+
+    let end = text[open.len()..].find(close)? + open.len();
+    let name = &text[open.len()..end];
+    // Template engines allow spaces inside double braces: {{ name }}
+    let name = if open == "{{" { name.trim() } else { name };
+    let is_name = name
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | ':' | '-' | '$'));
+
+    is_name.then_some(end + close.len())
+}
+
+/// printf-style: %s, %d, %5.2f, %-3ld, %1$s, %(name)s, %@ and %%
+fn printf_token_length(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut i = 1;
+
+    if bytes.get(i) == Some(&b'%') {
+        return Some(2);
+    }
+
+    // Python named placeholder: %(name)s
+    if bytes.get(i) == Some(&b'(') {
+        i += text[i..].find(')')? + 1;
+    }
+
+    // Positional argument: %1$s
+    let digits = count_while(&bytes[i..], |b| b.is_ascii_digit());
+    if digits > 0 && bytes.get(i + digits) == Some(&b'$') {
+        i += digits + 1;
+    }
+
+    i += count_while(&bytes[i..], |b| matches!(b, b'-' | b'+' | b'0' | b'#'));
+    i += count_while(&bytes[i..], |b| b.is_ascii_digit());
+    if bytes.get(i) == Some(&b'.') {
+        i += 1 + count_while(&bytes[i + 1..], |b| b.is_ascii_digit());
+    }
+    i += count_while(&bytes[i..], |b| {
+        matches!(b, b'h' | b'l' | b'L' | b'z' | b'j' | b't')
+    });
+
+    match bytes.get(i) {
+        Some(b's' | b'd' | b'i' | b'u' | b'f' | b'F' | b'e' | b'E' | b'g' | b'G' | b'x' | b'X')
+        | Some(b'o' | b'c' | b'p' | b'@') => Some(i + 1),
+        _ => None,
+    }
+}
+
+fn count_while(bytes: &[u8], predicate: impl Fn(u8) -> bool) -> usize {
+    bytes.iter().take_while(|&&b| predicate(b)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glossary_terms() {
+        let glossary = Glossary::parse("Tagent\ndashboard = панель мониторинга\n");
+        let (protected, hits) =
+            protect_with_glossary("Open the Tagent dashboard: {url}. Dashboard!", &glossary);
+        assert_eq!(protected.text, "Open the ⟦0⟧ ⟦1⟧: ⟦2⟧. ⟦3⟧!");
+        assert_eq!(
+            hits.iter()
+                .map(|hit| hit.translation.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Tagent", "панель мониторинга", "Панель мониторинга"]
+        );
+
+        let (restored, problems) = protected.restore("Откройте ⟦1⟧ ⟦0⟧: ⟦2⟧. ⟦3⟧!");
+        assert_eq!(
+            restored,
+            "Откройте панель мониторинга Tagent: {url}. Панель мониторинга!"
+        );
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_protect_and_restore() {
+        let protected = protect("Hello {name}, you have %d new {{ kind }} (%.1f%%) %1$s");
+        assert_eq!(
+            protected.text,
+            "Hello ⟦0⟧, you have ⟦1⟧ new ⟦2⟧ (⟦3⟧⟦4⟧) ⟦5⟧"
+        );
+
+        let (restored, problems) =
+            protected.restore("Привет ⟦0⟧, у вас ⟦ 1 ⟧ новых ⟦2⟧ (⟦3⟧⟦4⟧) ⟦5⟧");
+        assert_eq!(
+            restored,
+            "Привет {name}, у вас %d новых {{ kind }} (%.1f%%) %1$s"
+        );
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn test_protect_markup() {
+        let protected = protect(
+            "Click <b>Save</b> to store {count} items.<br/> See https://example.com/a_(b), \
+www.example.org. or write to support@example.com!",
+        );
+        assert_eq!(
+            protected.text,
+            "Click ⟦0⟧Save⟦1⟧ to store ⟦2⟧ items.⟦3⟧ See ⟦4⟧, ⟦5⟧. or write to ⟦6⟧!"
+        );
+        assert_eq!(
+            protected.tokens,
+            vec![
+                "<b>",
+                "</b>",
+                "{count}",
+                "<br/>",
+                "https://example.com/a_(b)",
+                "www.example.org",
+                "support@example.com"
+            ]
+        );
+
+        let protected = protect("(see http://example.com) <!-- note --> <a href=\"/x\">link</a>");
+        assert_eq!(protected.text, "(see ⟦0⟧) ⟦1⟧ ⟦2⟧link⟦3⟧");
+    }
+
+    #[test]
+    fn test_protect_markdown() {
+        let protected =
+            protect("Run `tagent hello`, see the [user guide](docs/guide.md) and ![logo][1].");
+        assert_eq!(
+            protected.text,
+            "Run ⟦0⟧, see the ⟦1⟧user guide⟦2⟧ and ⟦3⟧logo⟦4⟧."
+        );
+        assert_eq!(
+            protected.tokens,
+            vec!["`tagent hello`", "[", "](docs/guide.md)", "![", "][1]"]
+        );
+
+        // Brackets and backticks that are not Markdown markup stay text
+        let text = "[1] note, a ] b, it's `open and [x] done";
+        assert_eq!(protect(text).text, text);
+    }
+
+    #[test]
+    fn test_not_placeholders() {
+        let text = "100% sure, use { and } or {a b}, a < b > c, <3, user@host, x.www.y";
+        let protected = protect(text);
+        assert!(!protected.has_tokens());
+        assert_eq!(protected.text, text);
+    }
+
+    #[test]
+    fn test_dropped_and_duplicated_markers() {
+        let protected = protect("%(count)s files in {dir}");
+        assert_eq!(protected.text, "⟦0⟧ files in ⟦1⟧");
+
+        let (restored, problems) = protected.restore("⟦0⟧ файлов");
+        assert_eq!(restored, "%(count)s файлов");
+        assert_eq!(problems, vec!["{dir} was dropped by the provider"]);
+
+        let (restored, problems) = protected.restore("⟦0⟧ ⟦0⟧ файлов в ⟦1⟧");
+        assert_eq!(restored, "%(count)s %(count)s файлов в {dir}");
+        assert_eq!(problems, vec!["%(count)s was duplicated by the provider"]);
+    }
+}
@@ -0,0 +1,498 @@
+use super::{DictionaryEntry, LanguageInfo, Translation, TranslationProvider};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+// Caches opened in this process, one per file
+// Hotkey, interactive and CLI translators share the same store
+static OPEN_CACHES: OnceLock<Mutex<HashMap<PathBuf, Arc<TranslationCache>>>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+enum CachedValue {
+    Translation(Translation),
+    Dictionary(Option<DictionaryEntry>), // None = provider has no entry for the word
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheRecord {
+    created: i64,  // Unix time when the value was fetched
+    accessed: i64, // Unix time of the last hit, used for eviction
+    value: CachedValue,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    entries: HashMap<String, CacheRecord>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CacheLimits {
+    ttl_seconds: i64,   // 0 = entries never expire
+    max_entries: usize, // 0 = unlimited
+}
+
+/// Persistent translation cache stored as a JSON file
+pub struct TranslationCache {
+    path: PathBuf,
+    entries: Mutex<HashMap<String, CacheRecord>>,
+    limits: Mutex<CacheLimits>,
+    changed: Mutex<HashSet<String>>, // Keys added or used since the last flush
+}
+
+impl TranslationCache {
+    /// Open cache file (shared within the process) and apply limits
+    /// ttl_hours: 0 = entries never expire; max_entries: 0 = unlimited
+    pub fn open(path: &Path, ttl_hours: u64, max_entries: usize) -> Arc<Self> {
+        let limits = CacheLimits {
+            ttl_seconds: (ttl_hours * 3600) as i64,
+            max_entries,
+        };
+
+        let mut caches = OPEN_CACHES
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .unwrap();
+
+        let cache = caches
+            .entry(path.to_path_buf())
+            .or_insert_with(|| Arc::new(Self::load(path, limits)))
+            .clone();
+
+        // Config may have been reloaded with new limits
+        if let Ok(mut current) = cache.limits.lock() {
+            *current = limits;
+        }
+
+        cache
+    }
+
+    /// Load entries from disk
+    fn load(path: &Path, limits: CacheLimits) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            entries: Mutex::new(Self::read_entries(path)),
+            limits: Mutex::new(limits),
+            changed: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Entries of the cache file, a missing or damaged file gives an empty cache
+    fn read_entries(path: &Path) -> HashMap<String, CacheRecord> {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheFile>(&content).ok())
+            .map(|file| file.entries)
+            .unwrap_or_default()
+    }
+
+    /// Number of entries currently stored
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .map(|entries| entries.len())
+            .unwrap_or(0)
+    }
+
+    /// Cache file location
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Remove all entries and delete the cache file
+    /// Returns number of removed entries
+    pub fn clear(&self) -> Result<usize, Box<dyn Error>> {
+        let removed = {
+            let mut entries = self.entries.lock().unwrap();
+            let count = entries.len();
+            entries.clear();
+            self.changed.lock().unwrap().clear();
+            count
+        };
+
+        if self.path.exists() {
+            fs::remove_file(&self.path)?;
+        }
+
+        Ok(removed)
+    }
+
+    fn get(&self, key: &str) -> Option<CachedValue> {
+        let ttl_seconds = self.limits.lock().ok()?.ttl_seconds;
+        let now = chrono::Utc::now().timestamp();
+        let mut entries = self.entries.lock().ok()?;
+
+        let expired = match entries.get(key) {
+            Some(record) => ttl_seconds > 0 && now - record.created > ttl_seconds,
+            None => return None,
+        };
+
+        if expired {
+            entries.remove(key);
+            return None;
+        }
+
+        let record = entries.get_mut(key)?;
+        record.accessed = now;
+        if let Ok(mut changed) = self.changed.lock() {
+            changed.insert(key.to_string());
+        }
+        Some(record.value.clone())
+    }
+
+    fn insert(&self, key: String, value: CachedValue) -> Result<(), Box<dyn Error>> {
+        let limits = *self.limits.lock().unwrap();
+        let now = chrono::Utc::now().timestamp();
+
+        {
+            let mut entries = self.entries.lock().unwrap();
+            entries.insert(
+                key.clone(),
+                CacheRecord {
+                    created: now,
+                    accessed: now,
+                    value,
+                },
+            );
+            Self::prune(&mut entries, limits, now);
+            self.changed.lock().unwrap().insert(key);
+        }
+
+        // The hotkey app is usually ended by closing its window, save right away
+        self.flush()
+    }
+
+    /// Drop expired entries and evict least recently used ones over the limit
+    fn prune(entries: &mut HashMap<String, CacheRecord>, limits: CacheLimits, now: i64) {
+        if limits.ttl_seconds > 0 {
+            entries.retain(|_, record| now - record.created <= limits.ttl_seconds);
+        }
+
+        if limits.max_entries > 0 && entries.len() > limits.max_entries {
+            let mut by_access: Vec<(String, i64)> = entries
+                .iter()
+                .map(|(key, record)| (key.clone(), record.accessed))
+                .collect();
+            by_access.sort_by_key(|(_, accessed)| *accessed);
+
+            let excess = entries.len() - limits.max_entries;
+            for (key, _) in by_access.into_iter().take(excess) {
+                entries.remove(&key);
+            }
+        }
+    }
+
+    /// Save entries changed since the last flush
+    /// They are merged into the current file, so entries another process
+    /// (e.g. CLI next to the hotkey app) saved in the meantime are kept
+    pub fn flush(&self) -> Result<(), Box<dyn Error>> {
+        let limits = *self.limits.lock().unwrap();
+        let now = chrono::Utc::now().timestamp();
+
+        let mut entries = self.entries.lock().unwrap();
+        let mut changed = self.changed.lock().unwrap();
+
+        if changed.is_empty() {
+            return Ok(());
+        }
+
+        let mut merged = Self::read_entries(&self.path);
+        for key in changed.iter() {
+            if let Some(record) = entries.get(key) {
+                merged.insert(key.clone(), record.clone());
+            }
+        }
+        Self::prune(&mut merged, limits, now);
+
+        let file = CacheFile { entries: merged };
+        self.save(&serde_json::to_string(&file)?)?;
+
+        *entries = file.entries;
+        changed.clear();
+
+        Ok(())
+    }
+
+    /// Save all caches opened in this process (e.g. when the console window is closed)
+    pub fn flush_all() {
+        if let Some(Ok(caches)) = OPEN_CACHES.get().map(|caches| caches.lock()) {
+            for cache in caches.values() {
+                cache.flush().ok();
+            }
+        }
+    }
+
+    /// Write cache file through a temporary file so a crash never leaves it half-written
+    fn save(&self, content: &str) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = self.path.parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        let temp_path = self.path.with_extension("tmp");
+        fs::write(&temp_path, content)?;
+        fs::rename(&temp_path, &self.path)?;
+
+        Ok(())
+    }
+}
+
+/// Provider wrapper that serves repeated requests from the persistent cache
+pub struct CachedProvider {
+    inner: Box<dyn TranslationProvider>,
+    cache: Arc<TranslationCache>,
+}
+
+impl CachedProvider {
+    pub fn new(inner: Box<dyn TranslationProvider>, cache: Arc<TranslationCache>) -> Self {
+        Self { inner, cache }
+    }
+
+    /// Cache key: request kind, configured provider, language pair and text
+    fn key(&self, kind: &str, text: &str, from: &str, to: &str) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}",
+            kind,
+            self.inner.name(),
+            from,
+            to,
+            text
+        )
+    }
+}
+
+impl Drop for CachedProvider {
+    fn drop(&mut self) {
+        // Cache write failures must not break translation
+        self.cache.flush().ok();
+    }
+}
+
+#[async_trait]
+impl TranslationProvider for CachedProvider {
+    async fn translate_text(
+        &self,
+        text: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Translation, Box<dyn Error>> {
+        let key = self.key("translate", text, from, to);
+
+        if let Some(CachedValue::Translation(translation)) = self.cache.get(&key) {
+            return Ok(translation);
+        }
+
+        let translation = self.inner.translate_text(text, from, to).await?;
+
+        // Cache write failures must not break translation
+        self.cache
+            .insert(key, CachedValue::Translation(translation.clone()))
+            .ok();
+
+        Ok(translation)
+    }
+
+    async fn get_dictionary_entry(
+        &self,
+        word: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<DictionaryEntry>, Box<dyn Error>> {
+        let key = self.key("dictionary", word, from, to);
+
+        if let Some(CachedValue::Dictionary(entry)) = self.cache.get(&key) {
+            return Ok(entry);
+        }
+
+        let entry = self.inner.get_dictionary_entry(word, from, to).await?;
+
+        self.cache
+            .insert(key, CachedValue::Dictionary(entry.clone()))
+            .ok();
+
+        Ok(entry)
+    }
+
+    async fn get_supported_languages(&self) -> Result<Vec<LanguageInfo>, Box<dyn Error>> {
+        self.inner.get_supported_languages().await
+    }
+
+    async fn detect_language(&self, text: &str) -> Result<String, Box<dyn Error>> {
+        self.inner.detect_language(text).await
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn primary_name(&self) -> &str {
+        self.inner.primary_name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{Definition, PartOfSpeechEntry};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingProvider {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl TranslationProvider for CountingProvider {
+        async fn translate_text(
+            &self,
+            text: &str,
+            _from: &str,
+            _to: &str,
+        ) -> Result<Translation, Box<dyn Error>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Translation {
+                text: text.to_uppercase(),
+                provider: "Counting".to_string(),
+                detected_source_lang: None,
+                warnings: Vec::new(),
+                glossary_hits: Vec::new(),
+            })
+        }
+
+        async fn get_dictionary_entry(
+            &self,
+            word: &str,
+            _from: &str,
+            _to: &str,
+        ) -> Result<Option<DictionaryEntry>, Box<dyn Error>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Some(DictionaryEntry {
+                word: word.to_string(),
+                definitions: vec![PartOfSpeechEntry {
+                    part_of_speech: "noun".to_string(),
+                    definitions: vec![Definition {
+                        text: "кот".to_string(),
+                        synonyms: vec!["кошка".to_string()],
+                        frequency: None,
+                    }],
+                }],
+                provider: "Counting".to_string(),
+                ..DictionaryEntry::default()
+            }))
+        }
+
+        fn name(&self) -> &str {
+            "Counting"
+        }
+    }
+
+    fn temp_cache_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("tagent_cache_{}_{}.json", name, std::process::id()));
+        fs::remove_file(&path).ok();
+        path
+    }
+
+    fn cached_provider(cache: Arc<TranslationCache>) -> (CachedProvider, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let inner = Box::new(CountingProvider {
+            calls: calls.clone(),
+        });
+        (CachedProvider::new(inner, cache), calls)
+    }
+
+    #[tokio::test]
+    async fn test_repeated_requests_hit_cache() {
+        let path = temp_cache_path("hits");
+        let (provider, calls) = cached_provider(TranslationCache::open(&path, 0, 0));
+
+        for _ in 0..3 {
+            let translation = provider.translate_text("cat", "en", "ru").await.unwrap();
+            assert_eq!(translation.text, "CAT");
+            let entry = provider
+                .get_dictionary_entry("cat", "en", "ru")
+                .await
+                .unwrap();
+            assert_eq!(entry.unwrap().definitions[0].part_of_speech, "noun");
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Different language pair is a separate entry
+        provider.translate_text("cat", "en", "de").await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_cache_persists_structured_entries() {
+        let path = temp_cache_path("persist");
+        let (provider, _) = cached_provider(TranslationCache::open(&path, 0, 0));
+        provider
+            .get_dictionary_entry("cat", "en", "ru")
+            .await
+            .unwrap();
+        assert!(path.exists());
+        drop(provider);
+
+        // Fresh load from disk, bypassing the shared instance
+        let limits = CacheLimits {
+            ttl_seconds: 0,
+            max_entries: 0,
+        };
+        let (provider, calls) = cached_provider(Arc::new(TranslationCache::load(&path, limits)));
+        let entry = provider
+            .get_dictionary_entry("cat", "en", "ru")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert_eq!(entry.definitions[0].definitions[0].synonyms, vec!["кошка"]);
+
+        assert_eq!(provider.cache.clear().unwrap(), 1);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_max_entries_evicts_oldest() {
+        let path = temp_cache_path("evict");
+        let cache = TranslationCache::open(&path, 0, 2);
+        let (provider, _) = cached_provider(cache.clone());
+
+        provider.translate_text("one", "en", "ru").await.unwrap();
+        provider.translate_text("two", "en", "ru").await.unwrap();
+        provider.translate_text("three", "en", "ru").await.unwrap();
+        assert_eq!(cache.len(), 2);
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[tokio::test]
+    async fn test_flush_keeps_entries_of_other_processes() {
+        let path = temp_cache_path("merge");
+        let limits = CacheLimits {
+            ttl_seconds: 0,
+            max_entries: 0,
+        };
+
+        // Two processes with the same cache file
+        let (first, _) = cached_provider(Arc::new(TranslationCache::load(&path, limits)));
+        let (second, _) = cached_provider(Arc::new(TranslationCache::load(&path, limits)));
+        first.translate_text("one", "en", "ru").await.unwrap();
+        second.translate_text("two", "en", "ru").await.unwrap();
+        drop(first);
+        drop(second);
+
+        let (provider, calls) = cached_provider(Arc::new(TranslationCache::load(&path, limits)));
+        assert_eq!(provider.cache.len(), 2);
+        provider.translate_text("one", "en", "ru").await.unwrap();
+        provider.translate_text("two", "en", "ru").await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,296 @@
+use super::{DictionaryEntry, LanguageInfo, Translation, TranslationProvider};
+use async_trait::async_trait;
+use std::error::Error;
+
+/// Composite provider that tries each backend in order
+/// Any provider error (HTTP error, timeout, rate limiting, wrong key) moves on to the next provider
+/// Empty input is rejected once instead of being sent to every provider
+pub struct ProviderChain {
+    providers: Vec<Box<dyn TranslationProvider>>,
+    name: String,
+}
+
+impl ProviderChain {
+    pub fn new(providers: Vec<Box<dyn TranslationProvider>>) -> Self {
+        let name = providers
+            .iter()
+            .map(|p| p.name())
+            .collect::<Vec<_>>()
+            .join(" -> ");
+
+        Self { providers, name }
+    }
+
+    /// Combine errors of all failed providers into one message
+    fn combined_error(errors: Vec<String>) -> Box<dyn Error> {
+        format!("All translation providers failed: {}", errors.join("; ")).into()
+    }
+
+    /// Input error no provider can fix, reported without trying the chain
+    fn check_input(text: &str) -> Result<(), Box<dyn Error>> {
+        if text.trim().is_empty() {
+            return Err("Text is empty".into());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TranslationProvider for ProviderChain {
+    async fn translate_text(
+        &self,
+        text: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Translation, Box<dyn Error>> {
+        Self::check_input(text)?;
+        let mut errors = Vec::new();
+
+        for provider in &self.providers {
+            match provider.translate_text(text, from, to).await {
+                Ok(translation) => return Ok(translation),
+                Err(e) => errors.push(format!("{}: {}", provider.name(), e)),
+            }
+        }
+
+        Err(Self::combined_error(errors))
+    }
+
+    async fn get_dictionary_entry(
+        &self,
+        word: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<DictionaryEntry>, Box<dyn Error>> {
+        Self::check_input(word)?;
+        let mut errors = Vec::new();
+
+        // First provider with a dictionary entry wins
+        for provider in &self.providers {
+            match provider.get_dictionary_entry(word, from, to).await {
+                Ok(Some(entry)) => return Ok(Some(entry)),
+                Ok(None) => {}
+                Err(e) => errors.push(format!("{}: {}", provider.name(), e)),
+            }
+        }
+
+        if errors.len() == self.providers.len() {
+            Err(Self::combined_error(errors))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn get_supported_languages(&self) -> Result<Vec<LanguageInfo>, Box<dyn Error>> {
+        let mut errors = Vec::new();
+
+        for provider in &self.providers {
+            match provider.get_supported_languages().await {
+                Ok(languages) if !languages.is_empty() => return Ok(languages),
+                Ok(_) => {}
+                Err(e) => errors.push(format!("{}: {}", provider.name(), e)),
+            }
+        }
+
+        if errors.len() == self.providers.len() {
+            Err(Self::combined_error(errors))
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    async fn detect_language(&self, text: &str) -> Result<String, Box<dyn Error>> {
+        Self::check_input(text)?;
+        let mut errors = Vec::new();
+
+        for provider in &self.providers {
+            match provider.detect_language(text).await {
+                Ok(language) => return Ok(language),
+                Err(e) => errors.push(format!("{}: {}", provider.name(), e)),
+            }
+        }
+
+        Err(Self::combined_error(errors))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn primary_name(&self) -> &str {
+        self.providers.first().map(|p| p.name()).unwrap_or("")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::deepl::DeepLProvider;
+    use crate::providers::mock_server;
+
+    struct StubProvider {
+        name: &'static str,
+        result: Result<&'static str, &'static str>,
+    }
+
+    #[async_trait]
+    impl TranslationProvider for StubProvider {
+        async fn translate_text(
+            &self,
+            _text: &str,
+            _from: &str,
+            _to: &str,
+        ) -> Result<Translation, Box<dyn Error>> {
+            match self.result {
+                Ok(text) => Ok(Translation {
+                    text: text.to_string(),
+                    provider: self.name.to_string(),
+                    detected_source_lang: None,
+                    warnings: Vec::new(),
+                    glossary_hits: Vec::new(),
+                }),
+                Err(e) => Err(e.into()),
+            }
+        }
+
+        async fn get_dictionary_entry(
+            &self,
+            word: &str,
+            _from: &str,
+            _to: &str,
+        ) -> Result<Option<DictionaryEntry>, Box<dyn Error>> {
+            match self.result {
+                Ok(_) if self.name == "Dictionary" => Ok(Some(DictionaryEntry {
+                    word: word.to_string(),
+                    definitions: Vec::new(),
+                    provider: self.name.to_string(),
+                    ..DictionaryEntry::default()
+                })),
+                Ok(_) => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        }
+
+        async fn detect_language(&self, _text: &str) -> Result<String, Box<dyn Error>> {
+            match self.result {
+                Ok(language) => Ok(language.to_string()),
+                Err(e) => Err(e.into()),
+            }
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+    }
+
+    fn stub(
+        name: &'static str,
+        result: Result<&'static str, &'static str>,
+    ) -> Box<dyn TranslationProvider> {
+        Box::new(StubProvider { name, result })
+    }
+
+    #[tokio::test]
+    async fn test_first_provider_answers() {
+        let chain = ProviderChain::new(vec![stub("A", Ok("first")), stub("B", Ok("second"))]);
+        let translation = chain.translate_text("text", "en", "ru").await.unwrap();
+        assert_eq!(translation.text, "first");
+        assert_eq!(translation.provider, "A");
+    }
+
+    #[tokio::test]
+    async fn test_failover_to_next_provider() {
+        let chain = ProviderChain::new(vec![
+            stub("A", Err("HTTP error: 429 Too Many Requests")),
+            stub("B", Err("HTTP error: 503 Service Unavailable")),
+            stub("C", Ok("third")),
+        ]);
+        let translation = chain.translate_text("text", "en", "ru").await.unwrap();
+        assert_eq!(translation.text, "third");
+        assert_eq!(translation.provider, "C");
+        assert_eq!(chain.primary_name(), "A");
+        assert_eq!(chain.name(), "A -> B -> C");
+    }
+
+    #[tokio::test]
+    async fn test_all_providers_fail() {
+        let chain = ProviderChain::new(vec![
+            stub("A", Err("HTTP error: 502 Bad Gateway")),
+            stub("B", Err("DeepL translation quota exceeded")),
+        ]);
+        let err = chain.translate_text("text", "en", "ru").await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "All translation providers failed: A: HTTP error: 502 Bad Gateway; \
+             B: DeepL translation quota exceeded"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejected_request_falls_back() {
+        let (base_url, requests) = mock_server::start(vec![("403 Forbidden", "{}")]).await;
+        let deepl = DeepLProvider::new(mock_server::client(), "wrong-key", "free", &base_url);
+
+        let chain = ProviderChain::new(vec![Box::new(deepl), stub("Google", Ok("second"))]);
+        let translation = chain.translate_text("text", "en", "ru").await.unwrap();
+        assert_eq!(translation.provider, "Google");
+        assert_eq!(requests.await.unwrap().len(), 1);
+
+        // Configuration and parse errors fall back as well
+        let chain = ProviderChain::new(vec![
+            stub("A", Err("DeepL API key is not configured")),
+            stub("B", Err("expected value at line 1 column 1")),
+            stub("C", Ok("third")),
+        ]);
+        let translation = chain.translate_text("text", "en", "ru").await.unwrap();
+        assert_eq!(translation.provider, "C");
+    }
+
+    #[tokio::test]
+    async fn test_empty_text_is_not_sent_to_providers() {
+        let chain = ProviderChain::new(vec![stub("A", Ok("first")), stub("B", Ok("second"))]);
+        let err = chain.translate_text("  ", "en", "ru").await.unwrap_err();
+        assert_eq!(err.to_string(), "Text is empty");
+    }
+
+    #[tokio::test]
+    async fn test_detect_language_falls_back() {
+        let chain = ProviderChain::new(vec![
+            stub("A", Err("Failed to detect language")),
+            stub("B", Ok("de")),
+        ]);
+        assert_eq!(chain.detect_language("Hallo Welt").await.unwrap(), "de");
+
+        let chain = ProviderChain::new(vec![
+            stub("A", Err("Failed to detect language")),
+            stub("B", Err("HTTP error: 500 Internal Server Error")),
+        ]);
+        let err = chain.detect_language("text").await.unwrap_err();
+        assert!(err.to_string().contains("A: Failed to detect language"));
+        assert!(err.to_string().contains("B: HTTP error: 500"));
+    }
+
+    #[tokio::test]
+    async fn test_dictionary_skips_providers_without_entries() {
+        let chain = ProviderChain::new(vec![
+            stub("A", Ok("")),
+            stub("B", Err("HTTP error: 500 Internal Server Error")),
+            stub("Dictionary", Ok("")),
+        ]);
+        let entry = chain
+            .get_dictionary_entry("word", "en", "ru")
+            .await
+            .unwrap();
+        assert_eq!(entry.unwrap().provider, "Dictionary");
+
+        let chain = ProviderChain::new(vec![
+            stub("A", Ok("")),
+            stub("B", Err("HTTP error: 500 Internal Server Error")),
+        ]);
+        let entry = chain
+            .get_dictionary_entry("word", "en", "ru")
+            .await
+            .unwrap();
+        assert!(entry.is_none());
+    }
+}
@@ -0,0 +1,227 @@
+use super::{DictionaryEntry, Translation, TranslationProvider};
+use crate::languages;
+use async_trait::async_trait;
+use reqwest::{Client, StatusCode};
+use serde_json::Value;
+use std::error::Error;
+
+const FREE_API_URL: &str = "https://api-free.deepl.com";
+const PRO_API_URL: &str = "https://api.deepl.com";
+
+pub struct DeepLProvider {
+    client: Client,
+    api_key: String,
+    base_url: String,
+}
+
+impl DeepLProvider {
+    /// Create DeepL provider
+    /// plan: "free" or "pro"
+    /// base_url: API endpoint override (empty = derived from plan)
+    pub fn new(client: Client, api_key: &str, plan: &str, base_url: &str) -> Self {
+        let base_url = if !base_url.trim().is_empty() {
+            base_url.trim().trim_end_matches('/').to_string()
+        } else {
+            Self::default_url_for_plan(plan, api_key).to_string()
+        };
+
+        Self {
+            client,
+            api_key: api_key.trim().to_string(),
+            base_url,
+        }
+    }
+
+    /// Get API endpoint for the configured plan
+    /// DeepL free keys end with ":fx", so they select the free endpoint even if plan is not set
+    fn default_url_for_plan(plan: &str, api_key: &str) -> &'static str {
+        match plan.trim().to_lowercase().as_str() {
+            "pro" => PRO_API_URL,
+            "free" => FREE_API_URL,
+            _ if api_key.trim().ends_with(":fx") => FREE_API_URL,
+            _ => PRO_API_URL,
+        }
+    }
+
+    /// Convert language code to DeepL source language code
+    fn source_lang_code(code: &str) -> String {
+        // Source languages are always base codes (EN, PT, ZH)
+        code.split(['-', '_']).next().unwrap_or(code).to_uppercase()
+    }
+
+    /// Convert language code to DeepL target language code
+    fn target_lang_code(code: &str) -> String {
+        // DeepL requires a regional variant for English and Portuguese targets
+        languages::provider_code(code, "deepl").to_uppercase()
+    }
+
+    /// Convert DeepL HTTP error status into readable message
+    fn describe_error_status(status: StatusCode) -> String {
+        match status.as_u16() {
+            403 => "DeepL authorization failed (check DeepLApiKey and DeepLApiPlan)".to_string(),
+            429 => "DeepL rate limit exceeded, too many requests".to_string(),
+            456 => "DeepL translation quota exceeded".to_string(),
+            _ => format!("HTTP error: {}", status),
+        }
+    }
+}
+
+#[async_trait]
+impl TranslationProvider for DeepLProvider {
+    async fn translate_text(
+        &self,
+        text: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Translation, Box<dyn Error>> {
+        if self.api_key.is_empty() {
+            return Err("DeepL API key is not configured (set DeepLApiKey in [Provider])".into());
+        }
+
+        let url = format!("{}/v2/translate", self.base_url);
+
+        let mut params = vec![
+            ("text", text.to_string()),
+            ("target_lang", Self::target_lang_code(to)),
+        ];
+
+        // Omit source language to let DeepL detect it automatically
+        if from != "auto" {
+            params.push(("source_lang", Self::source_lang_code(from)));
+        }
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key))
+            .form(&params)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::describe_error_status(response.status()).into());
+        }
+
+        let body = response.text().await?;
+        let json: Value = serde_json::from_str(&body)?;
+
+        if let Some(translations) = json.get("translations").and_then(|v| v.as_array()) {
+            let result: String = translations
+                .iter()
+                .filter_map(|t| t.get("text").and_then(|v| v.as_str()))
+                .collect();
+
+            if result.is_empty() {
+                return Err("Failed to extract translation from response".into());
+            }
+
+            let detected_source_lang = translations
+                .first()
+                .and_then(|t| t.get("detected_source_language"))
+                .and_then(|v| v.as_str())
+                .map(|lang| lang.to_lowercase());
+
+            Ok(Translation {
+                text: result,
+                provider: self.name().to_string(),
+                detected_source_lang,
+                warnings: Vec::new(),
+                glossary_hits: Vec::new(),
+            })
+        } else {
+            Err("Invalid response format from DeepL".into())
+        }
+    }
+
+    async fn get_dictionary_entry(
+        &self,
+        _word: &str,
+        _from: &str,
+        _to: &str,
+    ) -> Result<Option<DictionaryEntry>, Box<dyn Error>> {
+        // DeepL API has no dictionary lookups
+        Ok(None)
+    }
+
+    fn name(&self) -> &str {
+        "DeepL"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock_server;
+
+    fn test_provider(base_url: &str) -> DeepLProvider {
+        DeepLProvider::new(mock_server::client(), "test-key:fx", "free", base_url)
+    }
+
+    #[tokio::test]
+    async fn test_translate_text() {
+        let (base_url, requests) = mock_server::start(vec![(
+            "200 OK",
+            r#"{"translations":[{"detected_source_language":"EN","text":"Привет, мир"}]}"#,
+        )])
+        .await;
+
+        let provider = test_provider(&base_url);
+        let result = provider
+            .translate_text("Hello, world", "en", "ru")
+            .await
+            .unwrap();
+        assert_eq!(result.text, "Привет, мир");
+        assert_eq!(result.detected_source_lang.as_deref(), Some("en"));
+
+        let request = &requests.await.unwrap()[0];
+        assert!(request.starts_with("POST /v2/translate"));
+        assert!(request.contains("DeepL-Auth-Key test-key:fx"));
+        assert!(request.contains("target_lang=RU"));
+        assert!(request.contains("source_lang=EN"));
+    }
+
+    #[tokio::test]
+    async fn test_auto_source_is_omitted() {
+        let (base_url, requests) =
+            mock_server::start(vec![("200 OK", r#"{"translations":[{"text":"Hello"}]}"#)]).await;
+
+        let provider = test_provider(&base_url);
+        let result = provider.translate_text("Привет", "auto", "en").await;
+        assert_eq!(result.unwrap().text, "Hello");
+
+        let request = &requests.await.unwrap()[0];
+        assert!(!request.contains("source_lang"));
+        assert!(request.contains("target_lang=EN-US"));
+    }
+
+    #[tokio::test]
+    async fn test_quota_error() {
+        let (base_url, _requests) = mock_server::start(vec![("456 Quota Exceeded", "{}")]).await;
+
+        let provider = test_provider(&base_url);
+        let err = provider
+            .translate_text("Hello", "en", "de")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("quota"));
+    }
+
+    #[tokio::test]
+    async fn test_dictionary_not_supported() {
+        let provider = test_provider("http://127.0.0.1:9");
+        let entry = provider.get_dictionary_entry("hello", "en", "ru").await;
+        assert!(entry.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_endpoint_selection() {
+        let provider = DeepLProvider::new(Client::new(), "key:fx", "", "");
+        assert_eq!(provider.base_url, FREE_API_URL);
+
+        let provider = DeepLProvider::new(Client::new(), "key", "pro", "");
+        assert_eq!(provider.base_url, PRO_API_URL);
+
+        let provider = DeepLProvider::new(Client::new(), "key", "free", "http://localhost:8080/");
+        assert_eq!(provider.base_url, "http://localhost:8080");
+    }
+}
@@ -1,21 +1,50 @@
-use super::{Definition, DictionaryEntry, PartOfSpeechEntry, TranslationProvider};
+use super::{
+    Definition, DictionaryEntry, Frequency, PartOfSpeechEntry, SourceDefinition,
+    SourceDefinitionEntry, Translation, TranslationProvider,
+};
+use crate::languages;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde_json::Value;
 use std::error::Error;
-use url::form_urlencoded;
 
 pub struct GoogleTranslateProvider {
     client: Client,
+    api_url: String,
 }
 
 impl GoogleTranslateProvider {
-    pub fn new() -> Self {
+    /// Create Google Translate provider
+    /// client: HTTP client configured from [Network] settings
+    /// api_url: translate_a/single endpoint
+    pub fn new(client: Client, api_url: &str) -> Self {
         Self {
-            client: Client::new(),
+            client,
+            api_url: api_url.trim().to_string(),
         }
     }
 
+    /// Send POST request to translate endpoint and parse JSON response
+    /// Text goes into the form body, so long texts are not limited by URL length
+    async fn request(&self, params: &str, text: &str) -> Result<Value, Box<dyn Error>> {
+        let full_url = format!("{}{}", self.api_url, params);
+
+        let response = self
+            .client
+            .post(&full_url)
+            .form(&[("q", text)])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(format!("HTTP error: {}", response.status()).into());
+        }
+
+        let body = response.text().await?;
+
+        Ok(serde_json::from_str(&body)?)
+    }
+
     /// Parse Google Translate dictionary response into DictionaryEntry
     fn parse_dictionary_response(&self, json: &Value) -> Option<DictionaryEntry> {
         let mut definitions = Vec::new();
@@ -33,8 +62,7 @@ impl GoogleTranslateProvider {
                             {
                                 let mut defs = Vec::new();
 
-                                for def in detailed_defs.iter().take(5) {
-                                    // Limit to 5 definitions per part of speech
+                                for def in detailed_defs {
                                     if let Some(def_array) = def.as_array() {
                                         if def_array.len() >= 2 {
                                             if let Some(definition) =
@@ -53,9 +81,16 @@ impl GoogleTranslateProvider {
                                                     Vec::new()
                                                 };
 
+                                                // Reverse translation score (fourth element)
+                                                let frequency = def_array
+                                                    .get(3)
+                                                    .and_then(|v| v.as_f64())
+                                                    .map(Frequency::from_score);
+
                                                 defs.push(Definition {
                                                     text: definition.to_string(),
                                                     synonyms,
+                                                    frequency,
                                                 });
                                             }
                                         }
@@ -78,21 +113,120 @@ impl GoogleTranslateProvider {
         if definitions.is_empty() {
             None
         } else {
-            // Get the word from translation (index 0)
-            let word = json
+            // First sentence of the translation (index 0): [translation, word, ...]
+            let sentence = json
                 .get(0)
                 .and_then(|v| v.as_array())
-                .and_then(|arr| arr.first())
-                .and_then(|v| v.get(0))
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string();
+                .and_then(|arr| arr.first());
+            let text_at = |index: usize| {
+                sentence
+                    .and_then(|v| v.get(index))
+                    .and_then(|v| v.as_str())
+                    .map(|text| text.trim().to_string())
+            };
+            let (translation_transliteration, transliteration) = parse_transliteration(json);
 
-            Some(DictionaryEntry { word, definitions })
+            Some(DictionaryEntry {
+                word: text_at(1).unwrap_or_default(),
+                translation: text_at(0).filter(|text| !text.is_empty()),
+                definitions,
+                provider: self.name().to_string(),
+                // Detected source language (at index 2)
+                detected_source_lang: json.get(2).and_then(|v| v.as_str()).map(String::from),
+                transliteration,
+                translation_transliteration,
+                source_definitions: parse_source_definitions(json),
+                examples: parse_examples(json),
+            })
         }
     }
 }
 
+/// Transliteration of the translation and of the word (dt=rm)
+/// Last sentence of index 0: [null, null, "krasivyy", "ˈbyo͞odəfəl"]
+fn parse_transliteration(json: &Value) -> (Option<String>, Option<String>) {
+    let transliteration = json
+        .get(0)
+        .and_then(|v| v.as_array())
+        .and_then(|sentences| {
+            sentences
+                .iter()
+                .find(|sentence| sentence.get(0).is_some_and(Value::is_null))
+        });
+    let text_at = |index: usize| {
+        transliteration
+            .and_then(|v| v.get(index))
+            .and_then(|v| v.as_str())
+            .filter(|text| !text.trim().is_empty())
+            .map(String::from)
+    };
+
+    (text_at(2), text_at(3))
+}
+
+/// Definitions in the source language (dt=md, index 12)
+/// [["noun", [["definition", "id", "example"], ...], "word"], ...]
+fn parse_source_definitions(json: &Value) -> Vec<SourceDefinitionEntry> {
+    let Some(entries) = json.get(12).and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let part_of_speech = entry.get(0)?.as_str()?.to_string();
+            let definitions: Vec<SourceDefinition> = entry
+                .get(1)?
+                .as_array()?
+                .iter()
+                .filter_map(|definition| {
+                    Some(SourceDefinition {
+                        text: definition.get(0)?.as_str()?.to_string(),
+                        example: definition.get(2).and_then(|v| v.as_str()).map(strip_tags),
+                    })
+                })
+                .collect();
+
+            (!definitions.is_empty()).then_some(SourceDefinitionEntry {
+                part_of_speech,
+                definitions,
+            })
+        })
+        .collect()
+}
+
+/// Usage examples (dt=ex, index 13): [[["the <b>word</b> in use", ...], ...]]
+fn parse_examples(json: &Value) -> Vec<String> {
+    json.get(13)
+        .and_then(|v| v.get(0))
+        .and_then(|v| v.as_array())
+        .map(|examples| {
+            examples
+                .iter()
+                .filter_map(|example| example.get(0)?.as_str())
+                .map(strip_tags)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Remove highlighting markup from examples ("<b>word</b>" -> "word")
+fn strip_tags(text: &str) -> String {
+    let mut result = String::new();
+    let mut in_tag = false;
+
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => result.push(c),
+            _ => {}
+        }
+    }
+
+    result
+}
+
 #[async_trait]
 impl TranslationProvider for GoogleTranslateProvider {
     async fn translate_text(
@@ -100,37 +234,14 @@ impl TranslationProvider for GoogleTranslateProvider {
         text: &str,
         from: &str,
         to: &str,
-    ) -> Result<String, Box<dyn Error>> {
-        let url = "https://translate.googleapis.com/translate_a/single";
-
-        let encoded_text = form_urlencoded::byte_serialize(text.as_bytes()).collect::<String>();
-
-        let from_param = if from == "auto" { "auto" } else { from };
-
+    ) -> Result<Translation, Box<dyn Error>> {
         let params = format!(
-            "?client=gtx&sl={}&tl={}&dt=t&q={}",
-            from_param, to, encoded_text
+            "?client=gtx&sl={}&tl={}&dt=t",
+            languages::provider_code(from, "google"),
+            languages::provider_code(to, "google")
         );
 
-        let full_url = format!("{}{}", url, params);
-
-        let response = self
-            .client
-            .get(&full_url)
-            .header(
-                "User-Agent",
-                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
-            )
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            return Err(format!("HTTP error: {}", response.status()).into());
-        }
-
-        let body = response.text().await?;
-
-        let json: Value = serde_json::from_str(&body)?;
+        let json = self.request(&params, text).await?;
 
         if let Some(translations) = json.get(0).and_then(|v| v.as_array()) {
             let mut result = String::new();
@@ -145,7 +256,14 @@ impl TranslationProvider for GoogleTranslateProvider {
                 return Err("Failed to extract translation from response".into());
             }
 
-            Ok(result)
+            Ok(Translation {
+                text: result,
+                provider: self.name().to_string(),
+                // Detected source language (at index 2)
+                detected_source_lang: json.get(2).and_then(|v| v.as_str()).map(String::from),
+                warnings: Vec::new(),
+                glossary_hits: Vec::new(),
+            })
         } else {
             Err("Invalid response format from Google Translate".into())
         }
@@ -157,40 +275,160 @@ impl TranslationProvider for GoogleTranslateProvider {
         from: &str,
         to: &str,
     ) -> Result<Option<DictionaryEntry>, Box<dyn Error>> {
-        let url = "https://translate.googleapis.com/translate_a/single";
-
-        let encoded_word = form_urlencoded::byte_serialize(word.as_bytes()).collect::<String>();
-        let from_param = if from == "auto" { "auto" } else { from };
-
         // Request additional data types for dictionary information
         let params = format!(
-            "?client=gtx&sl={}&tl={}&dt=t&dt=bd&dt=ex&dt=ld&dt=md&dt=qca&dt=rw&dt=rm&dt=ss&q={}",
-            from_param, to, encoded_word
+            "?client=gtx&sl={}&tl={}&dt=t&dt=bd&dt=ex&dt=ld&dt=md&dt=qca&dt=rw&dt=rm&dt=ss",
+            languages::provider_code(from, "google"),
+            languages::provider_code(to, "google")
         );
 
-        let full_url = format!("{}{}", url, params);
+        let json = self.request(&params, word).await?;
 
-        let response = self
-            .client
-            .get(&full_url)
-            .header(
-                "User-Agent",
-                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
-            )
-            .send()
-            .await?;
+        Ok(self.parse_dictionary_response(&json))
+    }
 
-        if !response.status().is_success() {
-            return Err(format!("HTTP error: {}", response.status()).into());
-        }
+    /// Detect language by translating the beginning of text with sl=auto
+    async fn detect_language(&self, text: &str) -> Result<String, Box<dyn Error>> {
+        let sample: String = text.chars().take(200).collect();
 
-        let body = response.text().await?;
-        let json: Value = serde_json::from_str(&body)?;
+        let json = self
+            .request("?client=gtx&sl=auto&tl=en&dt=t", &sample)
+            .await?;
 
-        Ok(self.parse_dictionary_response(&json))
+        // Detected source language (at index 2)
+        json.get(2)
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .ok_or_else(|| "Failed to detect language".into())
     }
 
     fn name(&self) -> &str {
         "Google Translate"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock_server;
+    use crate::vocabulary::{ExportFormat, Vocabulary};
+
+    #[tokio::test]
+    async fn test_long_text_is_sent_in_body() {
+        let (url, requests) = mock_server::start(vec![(
+            "200 OK",
+            r#"[[["Привет. ","Hello. ",null,null,10],["Мир","World",null,null,10]],null,"en"]"#,
+        )])
+        .await;
+
+        let provider = GoogleTranslateProvider::new(mock_server::client(), &url);
+        let text = "Hello. World".repeat(1000);
+        let translation = provider.translate_text(&text, "auto", "ru").await.unwrap();
+
+        assert_eq!(translation.text, "Привет. Мир");
+        assert_eq!(translation.detected_source_lang.as_deref(), Some("en"));
+
+        let requests = requests.await.unwrap();
+        let (headers, body) = requests[0].split_once("\r\n\r\n").unwrap();
+        assert!(headers.starts_with("POST /?client=gtx&sl=auto&tl=ru&dt=t HTTP/1.1"));
+        assert!(body.starts_with("q=Hello.+WorldHello."));
+        assert_eq!(body.len(), "q=".len() + "Hello.+World".len() * 1000);
+    }
+
+    #[tokio::test]
+    async fn test_detect_language_sends_sample() {
+        let (url, requests) = mock_server::start(vec![(
+            "200 OK",
+            r#"[[["Hello","Hallo",null,null,10]],null,"de"]"#,
+        )])
+        .await;
+
+        let provider = GoogleTranslateProvider::new(mock_server::client(), &url);
+        let text = "Hallo ".repeat(100);
+        assert_eq!(provider.detect_language(&text).await.unwrap(), "de");
+
+        let requests = requests.await.unwrap();
+        let (headers, body) = requests[0].split_once("\r\n\r\n").unwrap();
+        assert!(headers.starts_with("POST /?client=gtx&sl=auto&tl=en&dt=t HTTP/1.1"));
+        assert_eq!(body.len(), "q=".len() + "Hallo+".len() * 200 / 6);
+    }
+
+    #[test]
+    fn test_parse_dictionary_response() {
+        let json: Value = serde_json::from_str(
+            r#"[
+                [["красивый","beautiful",null,null,1],[null,null,"krasivyy","ˈbyo͞odəfəl"]],
+                [["adjective",["красивый","прекрасный"],[
+                    ["красивый",["beautiful","handsome","pretty"],null,0.34],
+                    ["прекрасный",["fine","beautiful"],null,0.05],
+                    ["изящный",["elegant","graceful"],null,0.002]
+                ],"beautiful",3]],
+                "en",null,null,null,1,[],[["en"],null,[1],["en"]],null,null,null,
+                [["adjective",[
+                    ["pleasing the senses or mind aesthetically.","m_en_1","<b>beautiful</b> young women"],
+                    ["of a very high standard; excellent.","m_en_2"]
+                ],"beautiful"]],
+                [[["she was the most <b>beautiful</b> woman",null,null,null,null,"ex_1"]]]
+            ]"#,
+        )
+        .unwrap();
+
+        let provider = GoogleTranslateProvider::new(Client::new(), "");
+        let entry = provider.parse_dictionary_response(&json).unwrap();
+
+        assert_eq!(entry.word, "beautiful");
+        assert_eq!(entry.translation.as_deref(), Some("красивый"));
+        assert_eq!(entry.detected_source_lang.as_deref(), Some("en"));
+
+        assert_eq!(entry.transliteration.as_deref(), Some("ˈbyo͞odəfəl"));
+        assert_eq!(
+            entry.translation_transliteration.as_deref(),
+            Some("krasivyy")
+        );
+
+        let definitions = &entry.definitions[0].definitions;
+        assert_eq!(definitions[0].synonyms, ["beautiful", "handsome", "pretty"]);
+        assert_eq!(definitions[0].frequency, Some(Frequency::Common));
+        assert_eq!(definitions[1].frequency, Some(Frequency::Uncommon));
+        assert_eq!(definitions[2].frequency, Some(Frequency::Rare));
+
+        let source_definitions = &entry.source_definitions[0];
+        assert_eq!(source_definitions.part_of_speech, "adjective");
+        assert_eq!(
+            source_definitions.definitions[0].example.as_deref(),
+            Some("beautiful young women")
+        );
+        assert_eq!(source_definitions.definitions[1].example, None);
+
+        assert_eq!(entry.examples, ["she was the most beautiful woman"]);
+    }
+
+    #[tokio::test]
+    async fn test_dictionary_entry_in_vocabulary() {
+        let (url, _requests) = mock_server::start(vec![(
+            "200 OK",
+            r#"[[["кот","cat",null,null,1]],[["noun",["кот","кошка"],[["кот",["cat","tomcat"],null,0.6],["кошка",["cat","pussy"],null,0.3]],"cat",1]],"en"]"#,
+        )])
+        .await;
+
+        let provider = GoogleTranslateProvider::new(mock_server::client(), &url);
+        let entry = provider
+            .get_dictionary_entry("cat", "auto", "ru")
+            .await
+            .unwrap()
+            .unwrap();
+
+        // The word is stored as looked up, not as its translation
+        let mut vocabulary = Vocabulary::default();
+        vocabulary.add(&entry, "en", "ru");
+        assert_eq!(vocabulary.words[0].word, "cat");
+        assert_eq!(
+            vocabulary.words[0].definitions[0].definitions[0].text,
+            "кот"
+        );
+
+        let tsv = vocabulary.export(ExportFormat::Tsv);
+        assert!(tsv
+            .contains("cat\t<b>Noun</b><br>кот [cat, tomcat]<br>кошка [cat, pussy]\ttagent en-ru"));
+    }
+}
@@ -0,0 +1,237 @@
+use super::{DictionaryEntry, LanguageInfo, Translation, TranslationProvider};
+use crate::languages;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::error::Error;
+
+pub struct LibreTranslateProvider {
+    client: Client,
+    base_url: String,
+    api_key: String,
+}
+
+impl LibreTranslateProvider {
+    /// Create LibreTranslate provider for a self-hosted server
+    /// api_key: optional key (empty = server without API keys)
+    pub fn new(client: Client, base_url: &str, api_key: &str) -> Self {
+        Self {
+            client,
+            base_url: base_url.trim().trim_end_matches('/').to_string(),
+            api_key: api_key.trim().to_string(),
+        }
+    }
+
+    /// Add API key to request body if configured
+    fn with_api_key(&self, mut body: Value) -> Value {
+        if !self.api_key.is_empty() {
+            body["api_key"] = Value::String(self.api_key.clone());
+        }
+        body
+    }
+
+    /// Send POST request with JSON body and parse JSON response
+    async fn post_json(&self, endpoint: &str, body: Value) -> Result<Value, Box<dyn Error>> {
+        let url = format!("{}/{}", self.base_url, endpoint);
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&self.with_api_key(body))
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        if !status.is_success() {
+            // LibreTranslate reports failures as {"error": "..."}
+            let message = serde_json::from_str::<Value>(&body)
+                .ok()
+                .and_then(|v| v.get("error").and_then(|e| e.as_str()).map(String::from));
+
+            return match message {
+                Some(message) => Err(format!("HTTP error: {} ({})", status, message).into()),
+                None => Err(format!("HTTP error: {}", status).into()),
+            };
+        }
+
+        Ok(serde_json::from_str(&body)?)
+    }
+}
+
+#[async_trait]
+impl TranslationProvider for LibreTranslateProvider {
+    async fn translate_text(
+        &self,
+        text: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Translation, Box<dyn Error>> {
+        // Resolve source language via /detect so "auto" works on any server version
+        let source = if from == "auto" {
+            self.detect_language(text).await?
+        } else {
+            languages::provider_code(from, "libretranslate")
+        };
+        let detected_source_lang = (from == "auto").then(|| source.clone());
+
+        let json = self
+            .post_json(
+                "translate",
+                json!({
+                    "q": text,
+                    "source": source,
+                    "target": languages::provider_code(to, "libretranslate"),
+                    "format": "text",
+                }),
+            )
+            .await?;
+
+        match json.get("translatedText").and_then(|v| v.as_str()) {
+            Some(translated) if !translated.is_empty() => Ok(Translation {
+                text: translated.to_string(),
+                provider: self.name().to_string(),
+                detected_source_lang,
+                warnings: Vec::new(),
+                glossary_hits: Vec::new(),
+            }),
+            Some(_) => Err("Failed to extract translation from response".into()),
+            None => Err("Invalid response format from LibreTranslate".into()),
+        }
+    }
+
+    async fn get_dictionary_entry(
+        &self,
+        _word: &str,
+        _from: &str,
+        _to: &str,
+    ) -> Result<Option<DictionaryEntry>, Box<dyn Error>> {
+        // LibreTranslate has no dictionary lookups
+        Ok(None)
+    }
+
+    /// Detect language of text using /detect endpoint
+    /// Returns language code with the highest confidence
+    async fn detect_language(&self, text: &str) -> Result<String, Box<dyn Error>> {
+        let json = self.post_json("detect", json!({ "q": text })).await?;
+
+        json.as_array()
+            .and_then(|candidates| {
+                candidates
+                    .iter()
+                    .filter_map(|c| {
+                        let language = c.get("language").and_then(|v| v.as_str())?;
+                        let confidence =
+                            c.get("confidence").and_then(|v| v.as_f64()).unwrap_or(0.0);
+                        Some((language, confidence))
+                    })
+                    .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            })
+            .map(|(language, _)| language.to_string())
+            .ok_or_else(|| "Failed to detect language".into())
+    }
+
+    async fn get_supported_languages(&self) -> Result<Vec<LanguageInfo>, Box<dyn Error>> {
+        let url = format!("{}/languages", self.base_url);
+
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(format!("HTTP error: {}", response.status()).into());
+        }
+
+        let body = response.text().await?;
+        let json: Value = serde_json::from_str(&body)?;
+
+        let languages = json
+            .as_array()
+            .ok_or("Invalid response format from LibreTranslate")?
+            .iter()
+            .filter_map(|lang| {
+                Some(LanguageInfo {
+                    code: lang.get("code")?.as_str()?.to_string(),
+                    name: lang.get("name")?.as_str()?.to_string(),
+                })
+            })
+            .collect();
+
+        Ok(languages)
+    }
+
+    fn name(&self) -> &str {
+        "LibreTranslate"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::mock_server;
+
+    #[tokio::test]
+    async fn test_translate_text() {
+        let (base_url, requests) =
+            mock_server::start(vec![("200 OK", r#"{"translatedText":"Hola"}"#)]).await;
+
+        let provider = LibreTranslateProvider::new(mock_server::client(), &base_url, "secret");
+        let result = provider.translate_text("Hello", "en", "es").await;
+        assert_eq!(result.unwrap().text, "Hola");
+
+        let request = &requests.await.unwrap()[0];
+        assert!(request.starts_with("POST /translate"));
+        assert!(request.contains(r#""source":"en""#));
+        assert!(request.contains(r#""target":"es""#));
+        assert!(request.contains(r#""api_key":"secret""#));
+    }
+
+    #[tokio::test]
+    async fn test_auto_source_uses_detect() {
+        let (base_url, requests) = mock_server::start(vec![
+            (
+                "200 OK",
+                r#"[{"confidence":12.0,"language":"uk"},{"confidence":90.0,"language":"ru"}]"#,
+            ),
+            ("200 OK", r#"{"translatedText":"Hello"}"#),
+        ])
+        .await;
+
+        let provider = LibreTranslateProvider::new(mock_server::client(), &base_url, "");
+        let result = provider.translate_text("Привет", "auto", "en").await.unwrap();
+        assert_eq!(result.text, "Hello");
+        assert_eq!(result.detected_source_lang.as_deref(), Some("ru"));
+
+        let requests = requests.await.unwrap();
+        assert!(requests[0].starts_with("POST /detect"));
+        assert!(!requests[0].contains("api_key"));
+        assert!(requests[1].contains(r#""source":"ru""#));
+    }
+
+    #[tokio::test]
+    async fn test_languages() {
+        let (base_url, _requests) = mock_server::start(vec![(
+            "200 OK",
+            r#"[{"code":"en","name":"English","targets":["ru"]},{"code":"ru","name":"Russian","targets":["en"]}]"#,
+        )])
+        .await;
+
+        let provider = LibreTranslateProvider::new(mock_server::client(), &base_url, "");
+        let languages = provider.get_supported_languages().await.unwrap();
+        assert_eq!(languages.len(), 2);
+        assert_eq!(languages[1].code, "ru");
+        assert_eq!(languages[1].name, "Russian");
+    }
+
+    #[tokio::test]
+    async fn test_error_message() {
+        let (base_url, _requests) =
+            mock_server::start(vec![("400 Bad Request", r#"{"error":"Invalid API key"}"#)]).await;
+
+        let provider = LibreTranslateProvider::new(mock_server::client(), &base_url, "");
+        let err = provider
+            .translate_text("Hello", "en", "es")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid API key"));
+    }
+}
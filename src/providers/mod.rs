@@ -1,25 +1,116 @@
+use crate::config::{Config, ConfigManager};
+use crate::detect;
+use crate::glossary::GlossaryHit;
+use crate::network;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::error::Error;
 
+pub mod cache;
+pub mod chain;
+pub mod deepl;
 pub mod google;
+pub mod libretranslate;
+
+#[cfg(test)]
+mod mock_server;
+
+// Translation result with the name of the provider that produced it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Translation {
+    pub text: String,
+    pub provider: String,
+    #[serde(default)]
+    pub detected_source_lang: Option<String>, // Source language reported by the provider
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>, // Problems found after translation (e.g. lost placeholders)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub glossary_hits: Vec<GlossaryHit>, // Glossary terms applied to the translation
+}
 
 // Common dictionary entry structure for all providers
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DictionaryEntry {
     pub word: String,
     pub definitions: Vec<PartOfSpeechEntry>,
+    pub provider: String, // Provider that returned the entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detected_source_lang: Option<String>, // Source language reported by the provider
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>, // Plain translation of the word
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transliteration: Option<String>, // Pronunciation of the word ("ˈbyo͞odəfəl")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation_transliteration: Option<String>, // Translation in Latin letters ("krasivyy")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_definitions: Vec<SourceDefinitionEntry>, // Meanings explained in the source language
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>, // Usage examples in the source language
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PartOfSpeechEntry {
     pub part_of_speech: String,
     pub definitions: Vec<Definition>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Definition {
     pub text: String,
-    pub synonyms: Vec<String>,
+    pub synonyms: Vec<String>, // Words of the source language translated as text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency: Option<Frequency>, // How often the word is translated as text
+}
+
+/// Share of source texts in which the word is translated this way
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Frequency {
+    Common,
+    Uncommon,
+    Rare,
+}
+
+impl Frequency {
+    /// Google reports a score from 0 to 1 for every translation of a word
+    pub fn from_score(score: f64) -> Self {
+        if score >= 0.1 {
+            Frequency::Common
+        } else if score >= 0.01 {
+            Frequency::Uncommon
+        } else {
+            Frequency::Rare
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Frequency::Common => "common",
+            Frequency::Uncommon => "uncommon",
+            Frequency::Rare => "rare",
+        }
+    }
+}
+
+/// Definitions in the source language for one part of speech
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceDefinitionEntry {
+    pub part_of_speech: String,
+    pub definitions: Vec<SourceDefinition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceDefinition {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<String>, // Phrase showing the meaning
+}
+
+// Language supported by a provider
+#[derive(Debug, Clone)]
+pub struct LanguageInfo {
+    pub code: String,
+    pub name: String,
 }
 
 // Main translation provider trait
@@ -31,7 +122,7 @@ pub trait TranslationProvider: Send + Sync {
         text: &str,
         from: &str,
         to: &str,
-    ) -> Result<String, Box<dyn Error>>;
+    ) -> Result<Translation, Box<dyn Error>>;
 
     /// Get dictionary entry for a single word
     /// Returns None if dictionary lookup is not supported or word not found
@@ -42,14 +133,92 @@ pub trait TranslationProvider: Send + Sync {
         to: &str,
     ) -> Result<Option<DictionaryEntry>, Box<dyn Error>>;
 
+    /// Get list of languages supported by the provider
+    /// Returns empty list if the provider does not report its languages
+    async fn get_supported_languages(&self) -> Result<Vec<LanguageInfo>, Box<dyn Error>> {
+        Ok(Vec::new())
+    }
+
+    /// Detect language of text, returns language code
+    /// Falls back to offline detection if the provider has no detection of its own
+    async fn detect_language(&self, text: &str) -> Result<String, Box<dyn Error>> {
+        detect::detect(text)
+            .map(String::from)
+            .ok_or_else(|| "Failed to detect language".into())
+    }
+
     /// Get provider name for display purposes
     fn name(&self) -> &str;
+
+    /// Get name of the provider that is tried first
+    /// Differs from name() only for fallback chains
+    fn primary_name(&self) -> &str {
+        self.name()
+    }
+}
+
+/// Create translation provider from TranslateProvider setting
+/// A comma-separated list (e.g. "deepl, google, libre") creates a fallback chain
+/// that tries each provider in order
+/// The result is wrapped in the persistent cache when [Cache] is enabled
+pub fn create_provider(
+    provider_setting: &str,
+    config: &Config,
+) -> Result<Box<dyn TranslationProvider>, Box<dyn Error>> {
+    let names: Vec<&str> = provider_setting
+        .split(',')
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    let provider = match names.len() {
+        0 => return Err("No translation provider configured".into()),
+        1 => create_single_provider(names[0], config)?,
+        _ => {
+            let providers = names
+                .iter()
+                .map(|name| create_single_provider(name, config))
+                .collect::<Result<Vec<_>, _>>()?;
+            Box::new(chain::ProviderChain::new(providers))
+        }
+    };
+
+    if !config.enable_cache {
+        return Ok(provider);
+    }
+
+    let cache = cache::TranslationCache::open(
+        &ConfigManager::get_cache_path(config),
+        config.cache_ttl_hours,
+        config.cache_max_entries,
+    );
+    Ok(Box::new(cache::CachedProvider::new(provider, cache)))
 }
 
 /// Create translation provider based on name
-pub fn create_provider(provider_name: &str) -> Result<Box<dyn TranslationProvider>, Box<dyn Error>> {
-    match provider_name.to_lowercase().as_str() {
-        "google" => Ok(Box::new(google::GoogleTranslateProvider::new())),
+/// Provider-specific settings (API keys, endpoints) are taken from config
+fn create_single_provider(
+    provider_name: &str,
+    config: &Config,
+) -> Result<Box<dyn TranslationProvider>, Box<dyn Error>> {
+    let client = network::build_client(config)?;
+
+    match provider_name.trim().to_lowercase().as_str() {
+        "google" => Ok(Box::new(google::GoogleTranslateProvider::new(
+            client,
+            &config.translate_api_url,
+        ))),
+        "deepl" => Ok(Box::new(deepl::DeepLProvider::new(
+            client,
+            &config.deepl_api_key,
+            &config.deepl_api_plan,
+            &config.deepl_api_url,
+        ))),
+        "libretranslate" | "libre" => Ok(Box::new(libretranslate::LibreTranslateProvider::new(
+            client,
+            &config.libretranslate_url,
+            &config.libretranslate_api_key,
+        ))),
         _ => Err(format!("Unknown translation provider: {}", provider_name).into()),
     }
 }
@@ -0,0 +1,251 @@
+use crate::vocabulary::VocabularyWord;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Words shown in one /quiz session
+pub const SESSION_SIZE: usize = 20;
+
+/// SM-2 review state of a vocabulary word
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewState {
+    pub repetitions: u32,           // Correct answers in a row
+    pub interval_days: u32,         // Days until the next review
+    pub ease_factor: f64,           // How fast the interval grows (>= 1.3)
+    pub due: Option<DateTime<Utc>>, // None = never reviewed, due immediately
+    pub last_review: Option<DateTime<Utc>>,
+}
+
+impl Default for ReviewState {
+    fn default() -> Self {
+        Self {
+            repetitions: 0,
+            interval_days: 0,
+            ease_factor: 2.5,
+            due: None,
+            last_review: None,
+        }
+    }
+}
+
+impl ReviewState {
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        !matches!(self.due, Some(due) if due > now)
+    }
+
+    /// Apply SM-2 algorithm for answer quality 0-5
+    /// Quality below 3 restarts the repetition sequence
+    pub fn review(&mut self, quality: u8, now: DateTime<Utc>) {
+        let quality = quality.min(5);
+
+        if quality >= 3 {
+            self.interval_days = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (self.interval_days as f64 * self.ease_factor).round() as u32,
+            };
+            self.repetitions += 1;
+        } else {
+            self.repetitions = 0;
+            self.interval_days = 1;
+        }
+
+        let q = (5 - quality) as f64;
+        self.ease_factor = (self.ease_factor + 0.1 - q * (0.08 + q * 0.02)).max(1.3);
+
+        self.last_review = Some(now);
+        self.due = Some(now + Duration::days(self.interval_days as i64));
+    }
+}
+
+/// Result of checking a quiz answer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnswerGrade {
+    Correct,  // Matches a stored translation
+    Close,    // Translation with a small typo
+    DontKnow, // Empty answer or "?"
+    Wrong,
+}
+
+impl AnswerGrade {
+    /// SM-2 quality for the grade
+    pub fn quality(&self) -> u8 {
+        match self {
+            AnswerGrade::Correct => 5,
+            AnswerGrade::Close => 3,
+            AnswerGrade::Wrong => 1,
+            AnswerGrade::DontKnow => 0,
+        }
+    }
+}
+
+/// Compare answer against stored translations
+/// Synonyms are not accepted: they are words of the source language translated the same way
+pub fn grade_answer(word: &VocabularyWord, answer: &str) -> AnswerGrade {
+    let answer = normalize(answer);
+    if answer.is_empty() || answer == "?" {
+        return AnswerGrade::DontKnow;
+    }
+
+    let translations: Vec<String> = word
+        .definitions
+        .iter()
+        .flat_map(|pos_entry| pos_entry.definitions.iter())
+        .map(|def| normalize(&def.text))
+        .collect();
+
+    if translations.contains(&answer) {
+        return AnswerGrade::Correct;
+    }
+
+    // One typo is forgiven in words of 4+ letters
+    let is_close = translations.iter().any(|translation| {
+        translation.chars().count() >= 4 && edit_distance(translation, &answer) <= 1
+    });
+
+    if is_close {
+        AnswerGrade::Close
+    } else {
+        AnswerGrade::Wrong
+    }
+}
+
+/// Indexes of words due for review, most overdue first (new words last)
+pub fn due_words(words: &[VocabularyWord], now: DateTime<Utc>, limit: usize) -> Vec<usize> {
+    let mut due: Vec<usize> = (0..words.len())
+        .filter(|&i| words[i].review.is_due(now))
+        .collect();
+
+    due.sort_by_key(|&i| (words[i].review.due.is_none(), words[i].review.due));
+    due.truncate(limit);
+    due
+}
+
+/// Earliest scheduled review among words that are not due yet
+pub fn next_review(words: &[VocabularyWord]) -> Option<DateTime<Utc>> {
+    words.iter().filter_map(|word| word.review.due).min()
+}
+
+/// Lowercase, trim and drop surrounding punctuation; "ё" is treated as "е"
+fn normalize(text: &str) -> String {
+    text.trim()
+        .trim_matches(|c: char| c.is_ascii_punctuation() && c != '?')
+        .trim()
+        .to_lowercase()
+        .replace('ё', "е")
+}
+
+/// Levenshtein distance by characters
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{Definition, PartOfSpeechEntry};
+
+    fn word(text: &str, translations: &[(&str, &[&str])]) -> VocabularyWord {
+        VocabularyWord {
+            word: text.to_string(),
+            source_lang: "en".to_string(),
+            target_lang: "ru".to_string(),
+            definitions: vec![PartOfSpeechEntry {
+                part_of_speech: "noun".to_string(),
+                definitions: translations
+                    .iter()
+                    .map(|(text, synonyms)| Definition {
+                        text: text.to_string(),
+                        synonyms: synonyms.iter().map(|s| s.to_string()).collect(),
+                        frequency: None,
+                    })
+                    .collect(),
+            }],
+            lookups: 1,
+            first_seen: Utc::now(),
+            last_seen: Utc::now(),
+            review: ReviewState::default(),
+        }
+    }
+
+    #[test]
+    fn test_grade_answer() {
+        let cat = word(
+            "cat",
+            &[("кот", &["cat", "tomcat"]), ("кошка", &["cat", "puss"])],
+        );
+
+        assert_eq!(grade_answer(&cat, " Кот "), AnswerGrade::Correct);
+        assert_eq!(grade_answer(&cat, "кошко"), AnswerGrade::Close);
+        assert_eq!(grade_answer(&cat, "собака"), AnswerGrade::Wrong);
+        assert_eq!(grade_answer(&cat, "?"), AnswerGrade::DontKnow);
+        assert_eq!(grade_answer(&cat, ""), AnswerGrade::DontKnow);
+
+        // Short words need an exact match
+        assert_eq!(grade_answer(&cat, "кит"), AnswerGrade::Wrong);
+
+        // The prompt word and other source language words are not translations
+        assert_eq!(grade_answer(&cat, "cat"), AnswerGrade::Wrong);
+        assert_eq!(grade_answer(&cat, "Puss"), AnswerGrade::Wrong);
+    }
+
+    #[test]
+    fn test_sm2_intervals() {
+        let now = Utc::now();
+        let mut state = ReviewState::default();
+        assert!(state.is_due(now));
+
+        state.review(5, now);
+        assert_eq!(state.interval_days, 1);
+        state.review(5, now);
+        assert_eq!(state.interval_days, 6);
+        state.review(5, now);
+        assert_eq!(state.interval_days, 16); // 6 * 2.7
+        assert!((state.ease_factor - 2.8).abs() < 1e-9);
+        assert!(!state.is_due(now));
+
+        // Failed answer restarts the sequence and lowers ease
+        state.review(1, now);
+        assert_eq!(state.repetitions, 0);
+        assert_eq!(state.interval_days, 1);
+        assert!((state.ease_factor - 2.26).abs() < 1e-9);
+
+        // Ease factor never drops below 1.3
+        for _ in 0..10 {
+            state.review(0, now);
+        }
+        assert!((state.ease_factor - 1.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_due_words_order() {
+        let now = Utc::now();
+        let mut words = vec![
+            word("new", &[("новый", &[])]),
+            word("late", &[("поздно", &[])]),
+            word("later", &[("позже", &[])]),
+            word("future", &[("будущее", &[])]),
+        ];
+        words[1].review.due = Some(now - Duration::days(3));
+        words[2].review.due = Some(now - Duration::days(1));
+        words[3].review.due = Some(now + Duration::days(2));
+
+        assert_eq!(due_words(&words, now, 10), vec![1, 2, 0]);
+        assert_eq!(due_words(&words, now, 1), vec![1]);
+        assert_eq!(next_review(&words[3..]), words[3].review.due);
+    }
+}
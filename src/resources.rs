@@ -0,0 +1,784 @@
+use crate::document::line_ending;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+/// Localization resource with entries that still need translation
+pub enum Resource {
+    Po(PoFile),
+    Json(JsonResource),
+}
+
+/// Resource content after filling in translations
+pub struct RenderedResource {
+    pub content: String,
+    pub translated: Vec<String>, // msgid or key path of every machine-translated entry
+    pub warnings: Vec<String>,   // Entries left untranslated and why
+}
+
+impl Resource {
+    /// Parse resource by file extension (.po, .pot, .json)
+    /// existing: current target file whose translations are kept;
+    /// gettext also keeps its header (Language, Plural-Forms)
+    pub fn parse(
+        path: &Path,
+        content: &str,
+        existing: Option<&str>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+
+        match extension.as_str() {
+            "po" | "pot" => Ok(Resource::Po(PoFile::parse(content, existing))),
+            "json" => Ok(Resource::Json(JsonResource::parse(content, existing)?)),
+            _ => Err(format!(
+                "Unsupported resource file: {} (expected .po, .pot or .json)",
+                path.display()
+            )
+            .into()),
+        }
+    }
+
+    /// Source texts of untranslated entries, without duplicates
+    pub fn untranslated(&self) -> Vec<String> {
+        let texts = match self {
+            Resource::Po(po) => po.untranslated(),
+            Resource::Json(json) => json.untranslated(),
+        };
+
+        let mut unique = Vec::new();
+        for text in texts {
+            if !unique.contains(&text) {
+                unique.push(text);
+            }
+        }
+        unique
+    }
+
+    /// Fill in translations (source text -> translation); missing ones stay untranslated
+    pub fn render(
+        &self,
+        translations: &HashMap<String, String>,
+    ) -> Result<RenderedResource, Box<dyn Error>> {
+        match self {
+            Resource::Po(po) => Ok(po.render(translations)),
+            Resource::Json(json) => json.render(translations),
+        }
+    }
+
+    /// gettext entries get the fuzzy flag, JSON has no place for it
+    pub fn supports_fuzzy(&self) -> bool {
+        matches!(self, Resource::Po(_))
+    }
+}
+
+/// gettext catalog; lines outside untranslated entries are kept byte for byte
+pub struct PoFile {
+    blocks: Vec<PoBlock>,
+    line_ending: &'static str,
+    nplurals: Option<usize>, // Plural forms of the target language, None = unknown
+    existing: HashMap<(Option<String>, String), PoEntry>, // Translated entries of the target catalog
+    existing_header: Option<Vec<String>>,                 // Header block of the target catalog
+}
+
+struct PoBlock {
+    lines: Vec<String>,
+    entry: Option<PoEntry>, // None = blank line, obsolete or unparsed block
+}
+
+struct PoEntry {
+    msgctxt: Option<String>,
+    msgid: String,
+    msgid_plural: Option<String>,
+    msgstr: Vec<String>,
+    fuzzy: bool,
+    msgstr_start: usize,       // First msgstr line, msgstr fields end the entry
+    keywords_start: usize,     // First non-comment line
+    flags_line: Option<usize>, // "#, c-format" line
+    previous_line: Option<usize>, // First "#|" line, flags go before it
+}
+
+impl PoEntry {
+    /// Catalog header: empty msgid without context
+    fn is_header(&self) -> bool {
+        self.msgid.is_empty() && self.msgctxt.is_none()
+    }
+
+    fn is_untranslated(&self) -> bool {
+        !self.is_header() && self.msgstr.iter().all(|s| s.is_empty())
+    }
+
+    /// Entries are identified by context and msgid, as msgmerge does
+    fn key(&self) -> (Option<String>, String) {
+        (self.msgctxt.clone(), self.msgid.clone())
+    }
+}
+
+impl PoFile {
+    /// existing: target catalog; its translations, header and number of plural forms are kept
+    pub fn parse(content: &str, existing: Option<&str>) -> Self {
+        let mut translated = HashMap::new();
+        let mut existing_header = None;
+
+        for block in existing.map(Self::parse_blocks).unwrap_or_default() {
+            match block.entry {
+                Some(entry) if entry.is_header() => existing_header = Some(block.lines),
+                Some(entry) if !entry.is_untranslated() => {
+                    translated.insert(entry.key(), entry);
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            blocks: Self::parse_blocks(content),
+            line_ending: line_ending(content),
+            nplurals: existing
+                .and_then(plural_forms)
+                .or_else(|| plural_forms(content)),
+            existing: translated,
+            existing_header,
+        }
+    }
+
+    fn parse_blocks(content: &str) -> Vec<PoBlock> {
+        let mut blocks = Vec::new();
+        let mut lines: Vec<String> = Vec::new();
+
+        for line in content.split_inclusive('\n') {
+            if line.trim().is_empty() {
+                if !lines.is_empty() {
+                    blocks.push(PoBlock::parse(std::mem::take(&mut lines)));
+                }
+                blocks.push(PoBlock {
+                    lines: vec![line.to_string()],
+                    entry: None,
+                });
+            } else {
+                lines.push(line.to_string());
+            }
+        }
+
+        if !lines.is_empty() {
+            blocks.push(PoBlock::parse(lines));
+        }
+
+        blocks
+    }
+
+    /// Translation of the entry in the target catalog
+    fn existing_entry(&self, entry: &PoEntry) -> Option<&PoEntry> {
+        self.existing
+            .get(&entry.key())
+            .filter(|existing| existing.msgid_plural.is_some() == entry.msgid_plural.is_some())
+    }
+
+    /// Number of msgstr fields, None = plural entry with unknown number of forms
+    fn field_count(&self, entry: &PoEntry) -> Option<usize> {
+        match entry.msgid_plural {
+            Some(_) => self
+                .nplurals
+                .or_else(|| self.existing_entry(entry).map(|e| e.msgstr.len())),
+            None => Some(1),
+        }
+    }
+
+    /// msgstr fields kept from the target catalog, None = still to be translated
+    fn existing_fields(&self, entry: &PoEntry) -> Vec<Option<String>> {
+        let existing = self.existing_entry(entry);
+        (0..self.field_count(entry).unwrap_or(0))
+            .map(|index| {
+                existing
+                    .and_then(|e| e.msgstr.get(index))
+                    .filter(|text| !text.is_empty())
+                    .cloned()
+            })
+            .collect()
+    }
+
+    /// Untranslated entries of the source file that can be filled in
+    fn open_entries(&self) -> impl Iterator<Item = &PoEntry> {
+        self.blocks
+            .iter()
+            .filter_map(|block| block.entry.as_ref())
+            .filter(|entry| entry.is_untranslated() && self.field_count(entry).is_some())
+    }
+
+    fn untranslated(&self) -> Vec<String> {
+        self.open_entries()
+            .filter(|entry| self.existing_fields(entry).iter().any(Option::is_none))
+            .flat_map(|entry| {
+                std::iter::once(entry.msgid.clone()).chain(entry.msgid_plural.clone())
+            })
+            .collect()
+    }
+
+    fn render(&self, translations: &HashMap<String, String>) -> RenderedResource {
+        let mut content = String::new();
+        let mut translated = Vec::new();
+        let mut warnings = Vec::new();
+
+        for block in &self.blocks {
+            let entry = block.entry.as_ref();
+
+            if let Some(entry) =
+                entry.filter(|e| e.is_untranslated() && self.field_count(e).is_none())
+            {
+                warnings.push(format!(
+                    "\"{}\" skipped: no Plural-Forms header with the number of plural forms",
+                    entry.msgid
+                ));
+            }
+
+            // Header of the target catalog (Language, Plural-Forms) replaces the template one
+            let is_header = entry.is_some_and(PoEntry::is_header);
+            if let Some(header) = self.existing_header.as_ref().filter(|_| is_header) {
+                header.iter().for_each(|line| content.push_str(line));
+                continue;
+            }
+
+            let rendered = entry
+                .filter(|e| e.is_untranslated() && self.field_count(e).is_some())
+                .and_then(|entry| {
+                    let mut fields = self.existing_fields(entry);
+                    let mut machine_translated = false;
+
+                    // Existing translations stay, only missing fields are machine-translated
+                    for (index, field) in fields.iter_mut().enumerate() {
+                        if field.is_none() {
+                            let source = match (index, &entry.msgid_plural) {
+                                (0, _) | (_, None) => &entry.msgid,
+                                (_, Some(msgid_plural)) => msgid_plural,
+                            };
+                            *field = translations.get(source).cloned();
+                            machine_translated |= field.is_some();
+                        }
+                    }
+
+                    if fields.iter().all(Option::is_none) {
+                        return None;
+                    }
+
+                    let fuzzy =
+                        machine_translated || self.existing_entry(entry).is_some_and(|e| e.fuzzy);
+                    let rendered = self.render_entry(block, entry, &fields, fuzzy);
+                    Some((entry, rendered, machine_translated))
+                });
+
+            match rendered {
+                Some((entry, rendered, machine_translated)) => {
+                    content.push_str(&rendered);
+                    if machine_translated {
+                        translated.push(entry.msgid.clone());
+                    }
+                }
+                None => block.lines.iter().for_each(|line| content.push_str(line)),
+            }
+        }
+
+        RenderedResource {
+            content,
+            translated,
+            warnings,
+        }
+    }
+
+    /// Entry with filled msgstr fields, marked fuzzy if requested
+    fn render_entry(
+        &self,
+        block: &PoBlock,
+        entry: &PoEntry,
+        fields: &[Option<String>], // One per form of the target language, None = empty
+        fuzzy: bool,
+    ) -> String {
+        let mut result = String::new();
+
+        for (index, line) in block.lines[..entry.msgstr_start].iter().enumerate() {
+            if !fuzzy {
+                result.push_str(line);
+                continue;
+            }
+            match entry.flags_line {
+                Some(flags_line) if flags_line == index => {
+                    if line.contains("fuzzy") {
+                        result.push_str(line);
+                    } else {
+                        result.push_str(line.trim_end());
+                        result.push_str(", fuzzy");
+                        result.push_str(self.line_ending);
+                    }
+                }
+                None if index == entry.previous_line.unwrap_or(entry.keywords_start) => {
+                    result.push_str("#, fuzzy");
+                    result.push_str(self.line_ending);
+                    result.push_str(line);
+                }
+                _ => result.push_str(line),
+            }
+        }
+
+        for (index, text) in fields.iter().enumerate() {
+            // Forms of the target language, not of the source (a .pot always has two)
+            let keyword = match entry.msgid_plural {
+                Some(_) => format!("msgstr[{}]", index),
+                None => "msgstr".to_string(),
+            };
+            let text = text.as_deref().unwrap_or("");
+            result.push_str(&format_po_field(&keyword, text, self.line_ending));
+        }
+
+        result
+    }
+}
+
+impl PoBlock {
+    fn parse(lines: Vec<String>) -> Self {
+        let entry = Self::parse_entry(&lines);
+        Self { lines, entry }
+    }
+
+    /// Entry with its msgstr fields, None for obsolete entries and unparsed blocks
+    fn parse_entry(lines: &[String]) -> Option<PoEntry> {
+        let mut msgctxt: Option<String> = None;
+        let mut msgid: Option<String> = None;
+        let mut msgid_plural: Option<String> = None;
+        let mut msgstr: Vec<String> = Vec::new();
+        let mut msgstr_start = None;
+        let mut keywords_start = None;
+        let mut flags_line = None;
+        let mut previous_line = None;
+        let mut fuzzy = false;
+        let mut current: Option<&mut String> = None;
+
+        for (index, line) in lines.iter().enumerate() {
+            let line = line.trim();
+
+            if line.starts_with("#~") {
+                return None; // Obsolete entry
+            }
+
+            if line.starts_with('#') {
+                if keywords_start.is_some() {
+                    return None;
+                }
+                if line.starts_with("#,") {
+                    fuzzy |= line.contains("fuzzy");
+                    flags_line = Some(index);
+                } else if line.starts_with("#|") && previous_line.is_none() {
+                    previous_line = Some(index);
+                }
+                continue;
+            }
+
+            keywords_start.get_or_insert(index);
+
+            if let Some(continuation) = line.strip_prefix('"') {
+                current.as_mut()?.push_str(&unescape_po(continuation)?);
+                continue;
+            }
+
+            let (keyword, value) = line.split_once(' ')?;
+            let value = unescape_po(value.trim().strip_prefix('"')?)?;
+
+            current = match keyword {
+                "msgctxt" => Some(msgctxt.insert(value)),
+                "msgid" => Some(msgid.insert(value)),
+                "msgid_plural" => Some(msgid_plural.insert(value)),
+                _ if keyword == "msgstr" || keyword.starts_with("msgstr[") => {
+                    msgstr_start.get_or_insert(index);
+                    msgstr.push(value);
+                    msgstr.last_mut()
+                }
+                _ => return None,
+            };
+        }
+
+        if msgstr.is_empty() {
+            return None;
+        }
+
+        Some(PoEntry {
+            msgctxt,
+            msgid: msgid?,
+            msgid_plural,
+            msgstr,
+            fuzzy,
+            msgstr_start: msgstr_start?,
+            keywords_start: keywords_start?,
+            flags_line,
+            previous_line,
+        })
+    }
+}
+
+/// Number of plural forms from the "Plural-Forms: nplurals=3; ..." header
+/// None for templates ("nplurals=INTEGER") and catalogs without the header
+fn plural_forms(content: &str) -> Option<usize> {
+    let header = content
+        .lines()
+        .find(|line| line.contains("Plural-Forms:"))?;
+    let value = header.split("nplurals=").nth(1)?.trim_start();
+    let digits: String = value.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok().filter(|&count| count > 0)
+}
+
+/// Content of a quoted PO string without the opening quote: `text\n"`
+fn unescape_po(quoted: &str) -> Option<String> {
+    let inner = quoted.trim_end().strip_suffix('"')?;
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => result.push('\n'),
+            't' => result.push('\t'),
+            'r' => result.push('\r'),
+            other => result.push(other), // \" and \\
+        }
+    }
+
+    Some(result)
+}
+
+fn escape_po(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+        .replace('\r', "\\r")
+}
+
+/// `msgstr "text"`, multi-line text is split after each \n like msgmerge does
+fn format_po_field(keyword: &str, text: &str, line_ending: &str) -> String {
+    let pieces: Vec<&str> = text.split_inclusive('\n').collect();
+
+    if pieces.len() <= 1 {
+        return format!("{} \"{}\"{}", keyword, escape_po(text), line_ending);
+    }
+
+    let mut result = format!("{} \"\"{}", keyword, line_ending);
+    for piece in pieces {
+        result.push_str(&format!("\"{}\"{}", escape_po(piece), line_ending));
+    }
+    result
+}
+
+/// Flat or nested JSON locale file
+pub struct JsonResource {
+    value: Value,                           // Source structure with existing translations
+    pending: Vec<(String, String, String)>, // JSON pointer, key path, source text
+}
+
+impl JsonResource {
+    /// existing: current target file, its non-empty strings are kept
+    pub fn parse(content: &str, existing: Option<&str>) -> Result<Self, Box<dyn Error>> {
+        let source: Value = serde_json::from_str(content)?;
+        let existing: Option<Value> = match existing {
+            Some(existing) => Some(
+                serde_json::from_str(existing)
+                    .map_err(|e| format!("Failed to parse existing translation: {}", e))?,
+            ),
+            None => None,
+        };
+
+        let mut resource = Self {
+            value: source.clone(),
+            pending: Vec::new(),
+        };
+        resource.collect(&source, existing.as_ref(), String::new(), String::new());
+
+        Ok(resource)
+    }
+
+    fn collect(&mut self, value: &Value, existing: Option<&Value>, pointer: String, path: String) {
+        match value {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    let escaped = key.replace('~', "~0").replace('/', "~1");
+                    let child_path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", path, key)
+                    };
+                    self.collect(
+                        child,
+                        existing,
+                        format!("{}/{}", pointer, escaped),
+                        child_path,
+                    );
+                }
+            }
+            Value::Array(items) => {
+                for (index, child) in items.iter().enumerate() {
+                    let child_path = format!("{}[{}]", path, index);
+                    self.collect(
+                        child,
+                        existing,
+                        format!("{}/{}", pointer, index),
+                        child_path,
+                    );
+                }
+            }
+            Value::String(text) if !text.trim().is_empty() => {
+                let translation = existing
+                    .and_then(|existing| existing.pointer(&pointer))
+                    .and_then(|value| value.as_str())
+                    .filter(|translation| !translation.trim().is_empty());
+
+                match translation {
+                    Some(translation) => {
+                        if let Some(target) = self.value.pointer_mut(&pointer) {
+                            *target = Value::String(translation.to_string());
+                        }
+                    }
+                    None => self.pending.push((pointer, path, text.clone())),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn untranslated(&self) -> Vec<String> {
+        self.pending
+            .iter()
+            .map(|(_, _, text)| text.clone())
+            .collect()
+    }
+
+    fn render(
+        &self,
+        translations: &HashMap<String, String>,
+    ) -> Result<RenderedResource, Box<dyn Error>> {
+        let mut value = self.value.clone();
+        let mut translated = Vec::new();
+
+        for (pointer, path, text) in &self.pending {
+            if let (Some(translation), Some(target)) =
+                (translations.get(text), value.pointer_mut(pointer))
+            {
+                *target = Value::String(translation.clone());
+                translated.push(path.clone());
+            }
+        }
+
+        Ok(RenderedResource {
+            content: serde_json::to_string_pretty(&value)? + "\n",
+            translated,
+            warnings: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translations(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(source, target)| (source.to_string(), target.to_string()))
+            .collect()
+    }
+
+    const PO: &str = r#"msgid ""
+msgstr ""
+"Language: ru\n"
+"Plural-Forms: nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : 1);\n"
+
+#: src/main.rs:10
+msgid "Hello, {name}!"
+msgstr ""
+
+#: src/main.rs:20
+#, c-format
+msgid "%d file"
+msgid_plural "%d files"
+msgstr[0] ""
+msgstr[1] ""
+msgstr[2] ""
+
+msgid "Already translated"
+msgstr "Уже переведено"
+
+msgctxt "menu"
+msgid ""
+"First line\n"
+"second line"
+msgstr ""
+
+#~ msgid "Obsolete"
+#~ msgstr ""
+"#;
+
+    #[test]
+    fn test_po_untranslated_entries() {
+        let resource = Resource::parse(Path::new("ru.po"), PO, None).unwrap();
+        assert!(resource.supports_fuzzy());
+        assert_eq!(
+            resource.untranslated(),
+            vec![
+                "Hello, {name}!",
+                "%d file",
+                "%d files",
+                "First line\nsecond line"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_po_render() {
+        let resource = Resource::parse(Path::new("ru.po"), PO, None).unwrap();
+        let rendered = resource
+            .render(&translations(&[
+                ("Hello, {name}!", "Привет, {name}!"),
+                ("%d file", "%d файл"),
+                ("%d files", "%d файлов"),
+            ]))
+            .unwrap();
+
+        assert_eq!(rendered.translated, vec!["Hello, {name}!", "%d file"]);
+        assert!(rendered.content.contains(
+            "#: src/main.rs:10\n#, fuzzy\nmsgid \"Hello, {name}!\"\nmsgstr \"Привет, {name}!\"\n"
+        ));
+        assert!(rendered.content.contains(
+            "#, c-format, fuzzy\nmsgid \"%d file\"\nmsgid_plural \"%d files\"\n\
+msgstr[0] \"%d файл\"\nmsgstr[1] \"%d файлов\"\nmsgstr[2] \"%d файлов\"\n"
+        ));
+
+        // Entries without translation, header and obsolete entries are unchanged
+        assert!(rendered
+            .content
+            .starts_with("msgid \"\"\nmsgstr \"\"\n\"Language: ru\\n\"\n"));
+        assert!(rendered.content.contains("\"second line\"\nmsgstr \"\"\n"));
+        assert!(rendered
+            .content
+            .ends_with("#~ msgid \"Obsolete\"\n#~ msgstr \"\"\n"));
+    }
+
+    #[test]
+    fn test_po_plural_forms_of_target_language() {
+        let pot = "msgid \"\"\nmsgstr \"\"\n\"Plural-Forms: nplurals=INTEGER; plural=EXPRESSION;\\n\"\n\n\
+msgid \"%d file\"\nmsgid_plural \"%d files\"\nmsgstr[0] \"\"\nmsgstr[1] \"\"\n\n\
+msgid \"Open\"\nmsgstr \"\"\n";
+        let plurals = translations(&[
+            ("%d file", "%d plik"),
+            ("%d files", "%d plików"),
+            ("Open", "Otwórz"),
+        ]);
+
+        // Template without a target catalog: plural entries are skipped, not written with two forms
+        let resource = Resource::parse(Path::new("app.pot"), pot, None).unwrap();
+        assert_eq!(resource.untranslated(), vec!["Open"]);
+        let rendered = resource.render(&plurals).unwrap();
+        assert_eq!(rendered.translated, vec!["Open"]);
+        assert!(rendered
+            .content
+            .contains("msgstr[0] \"\"\nmsgstr[1] \"\"\n"));
+        assert_eq!(rendered.warnings.len(), 1);
+        assert!(rendered.warnings[0].contains("%d file"));
+
+        // Number of forms comes from the target catalog
+        let existing =
+            "msgid \"\"\nmsgstr \"\"\n\"Plural-Forms: nplurals=3; plural=(n==1 ? 0 : 1);\\n\"\n";
+        let resource = Resource::parse(Path::new("app.pot"), pot, Some(existing)).unwrap();
+        let rendered = resource.render(&plurals).unwrap();
+        assert!(rendered
+            .content
+            .contains("msgstr[0] \"%d plik\"\nmsgstr[1] \"%d plików\"\nmsgstr[2] \"%d plików\"\n"));
+        assert!(rendered.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_po_keeps_existing_translations() {
+        let pot = "msgid \"\"\nmsgstr \"\"\n\"Plural-Forms: nplurals=INTEGER; plural=EXPRESSION;\\n\"\n\n\
+msgid \"Open\"\nmsgstr \"\"\n\n\
+msgctxt \"verb\"\nmsgid \"Save\"\nmsgstr \"\"\n\n\
+msgctxt \"noun\"\nmsgid \"Save\"\nmsgstr \"\"\n\n\
+msgid \"%d file\"\nmsgid_plural \"%d files\"\nmsgstr[0] \"\"\nmsgstr[1] \"\"\n\n\
+msgid \"Close\"\nmsgstr \"\"\n";
+        let existing = "msgid \"\"\nmsgstr \"\"\n\"Language: ru\\n\"\n\
+\"Plural-Forms: nplurals=3; plural=(n%10==1 ? 0 : 1);\\n\"\n\n\
+msgid \"Open\"\nmsgstr \"Открыть\"\n\n\
+#, fuzzy\nmsgctxt \"verb\"\nmsgid \"Save\"\nmsgstr \"Сохранить\"\n\n\
+msgid \"%d file\"\nmsgid_plural \"%d files\"\n\
+msgstr[0] \"%d файл\"\nmsgstr[1] \"%d файла\"\nmsgstr[2] \"\"\n\n\
+msgid \"Close\"\nmsgstr \"\"\n";
+
+        let resource = Resource::parse(Path::new("app.pot"), pot, Some(existing)).unwrap();
+        assert_eq!(
+            resource.untranslated(),
+            vec!["Save", "%d file", "%d files", "Close"]
+        );
+
+        let rendered = resource
+            .render(&translations(&[
+                ("Save", "Сохранение"),
+                ("%d file", "%d файл (MT)"),
+                ("%d files", "%d файлов"),
+                ("Close", "Закрыть"),
+            ]))
+            .unwrap();
+        assert_eq!(rendered.translated, vec!["Save", "%d file", "Close"]);
+
+        // Header and translations of the target catalog are kept, fuzzy only where it was
+        assert!(rendered
+            .content
+            .starts_with("msgid \"\"\nmsgstr \"\"\n\"Language: ru\\n\"\n"));
+        assert!(rendered
+            .content
+            .contains("\n\nmsgid \"Open\"\nmsgstr \"Открыть\"\n"));
+        assert!(rendered
+            .content
+            .contains("#, fuzzy\nmsgctxt \"verb\"\nmsgid \"Save\"\nmsgstr \"Сохранить\"\n"));
+        assert!(rendered
+            .content
+            .contains("#, fuzzy\nmsgctxt \"noun\"\nmsgid \"Save\"\nmsgstr \"Сохранение\"\n"));
+        assert!(rendered.content.contains(
+            "#, fuzzy\nmsgid \"%d file\"\nmsgid_plural \"%d files\"\n\
+msgstr[0] \"%d файл\"\nmsgstr[1] \"%d файла\"\nmsgstr[2] \"%d файлов\"\n"
+        ));
+        assert!(rendered
+            .content
+            .contains("#, fuzzy\nmsgid \"Close\"\nmsgstr \"Закрыть\"\n"));
+    }
+
+    #[test]
+    fn test_po_multiline_msgstr() {
+        assert_eq!(
+            format_po_field("msgstr", "Первая строка\nвторая \"строка\"", "\n"),
+            "msgstr \"\"\n\"Первая строка\\n\"\n\"вторая \\\"строка\\\"\"\n"
+        );
+    }
+
+    #[test]
+    fn test_json_keeps_existing_translations() {
+        let source = r#"{"title": "Settings", "menu": {"open": "Open {file}", "items": ["Copy", ""]}, "count": 3}"#;
+        let existing = r#"{"title": "Настройки", "menu": {"open": ""}}"#;
+
+        let resource = Resource::parse(Path::new("en.json"), source, Some(existing)).unwrap();
+        assert!(!resource.supports_fuzzy());
+        assert_eq!(resource.untranslated(), vec!["Open {file}", "Copy"]);
+
+        let rendered = resource
+            .render(&translations(&[("Open {file}", "Открыть {file}")]))
+            .unwrap();
+        let value: Value = serde_json::from_str(&rendered.content).unwrap();
+
+        assert_eq!(rendered.translated, vec!["menu.open"]);
+        assert_eq!(value["title"], "Настройки");
+        assert_eq!(value["menu"]["open"], "Открыть {file}");
+        assert_eq!(value["menu"]["items"][0], "Copy");
+        assert_eq!(value["count"], 3);
+
+        // Key order of the source file is preserved
+        assert!(rendered.content.find("title") < rendered.content.find("menu"));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::config::Config;
+use crate::network;
 use reqwest::Client;
 use rodio::{Decoder, OutputStreamBuilder, Sink};
 use std::io::Cursor;
@@ -7,7 +9,6 @@ use std::time::Duration;
 
 const TTS_API_URL: &str = "https://translate.google.com/translate_tts";
 const MAX_TEXT_LENGTH: usize = 100;
-const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36";
 
 #[derive(Debug)]
 pub enum SpeechError {
@@ -33,13 +34,11 @@ pub struct SpeechManager {
 }
 
 impl SpeechManager {
-    pub fn new() -> Self {
-        Self {
-            client: Client::builder()
-                .timeout(Duration::from_secs(10))
-                .build()
-                .expect("Failed to create HTTP client for speech"),
-        }
+    /// Create speech manager using [Network] settings (timeouts, proxy, User-Agent)
+    pub fn new(config: &Config) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            client: network::build_client(config)?,
+        })
     }
 
     /// Fetch TTS audio from Google Translate API
@@ -66,7 +65,6 @@ impl SpeechManager {
         let response = self
             .client
             .get(&url)
-            .header("User-Agent", USER_AGENT)
             .send()
             .await
             .map_err(|e| SpeechError::NetworkError(format!("Failed to fetch TTS audio: {}", e)))?;
@@ -240,7 +238,7 @@ mod tests {
 
     #[test]
     fn test_split_text_short() {
-        let manager = SpeechManager::new();
+        let manager = SpeechManager::new(&Config::default()).unwrap();
         let text = "Hello world";
         let chunks = manager.split_text_for_tts(text);
         assert_eq!(chunks.len(), 1);
@@ -249,7 +247,7 @@ mod tests {
 
     #[test]
     fn test_split_text_long() {
-        let manager = SpeechManager::new();
+        let manager = SpeechManager::new(&Config::default()).unwrap();
         let text = "a".repeat(250);
         let chunks = manager.split_text_for_tts(&text);
         assert!(chunks.len() >= 3);
@@ -260,7 +258,7 @@ mod tests {
 
     #[test]
     fn test_split_text_sentences() {
-        let manager = SpeechManager::new();
+        let manager = SpeechManager::new(&Config::default()).unwrap();
         let text = "First sentence. Second sentence. Third sentence.";
         let chunks = manager.split_text_for_tts(text);
         assert!(chunks.len() >= 1);
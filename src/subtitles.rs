@@ -0,0 +1,122 @@
+use crate::document::{push_trimmed, push_verbatim, Segment};
+
+/// Split SRT or WebVTT subtitles into cue texts to translate
+/// Indices, cue identifiers, timestamps, WEBVTT header and NOTE/STYLE blocks stay verbatim
+pub fn split(content: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut cue_text = String::new();
+    let mut after_timing = false;
+
+    for line in content.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            flush_cue(&mut segments, &mut cue_text);
+            push_verbatim(&mut segments, line);
+            after_timing = false;
+        } else if after_timing {
+            cue_text.push_str(line);
+        } else {
+            // Index or identifier, timing line, or a block without cues
+            after_timing = is_timing_line(line);
+            push_verbatim(&mut segments, line);
+        }
+    }
+
+    flush_cue(&mut segments, &mut cue_text);
+    segments
+}
+
+/// Stack original and translated cue lines for bilingual subtitles
+pub fn bilingual(original: &str, translation: &str, line_ending: &str) -> String {
+    format!("{}{}{}", original, line_ending, translation)
+}
+
+/// "00:00:01,000 --> 00:00:03,500" (SRT) or "00:01.000 --> 00:03.500 line:90%" (WebVTT)
+fn is_timing_line(line: &str) -> bool {
+    line.split_once("-->").is_some_and(|(start, end)| {
+        is_timestamp(start.trim()) && end.split_whitespace().next().is_some_and(is_timestamp)
+    })
+}
+
+fn is_timestamp(value: &str) -> bool {
+    value.contains(':')
+        && value
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, ':' | ',' | '.'))
+}
+
+fn flush_cue(segments: &mut Vec<Segment>, cue_text: &mut String) {
+    if !cue_text.is_empty() {
+        push_trimmed(segments, cue_text);
+        cue_text.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::{join, line_ending};
+
+    fn texts(segments: &[Segment]) -> Vec<String> {
+        segments
+            .iter()
+            .filter_map(|segment| match segment {
+                Segment::Text(text) => Some(text.clone()),
+                Segment::Verbatim(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_srt_cues() {
+        let content = "1\r\n00:00:01,000 --> 00:00:03,500\r\nHello there!\r\nHow are you?\r\n\r\n\
+2\r\n00:00:04,000 --> 00:00:05,000\r\n<i>Fine.</i>\r\n";
+
+        let segments = split(content);
+        assert_eq!(
+            texts(&segments),
+            vec!["Hello there!\r\nHow are you?", "<i>Fine.</i>"]
+        );
+        assert_eq!(join(&segments, &[]), content);
+
+        let translated = join(
+            &segments,
+            &[
+                "Привет!\r\nКак дела?".to_string(),
+                "<i>Хорошо.</i>".to_string(),
+            ],
+        );
+        assert!(translated.starts_with("1\r\n00:00:01,000 --> 00:00:03,500\r\nПривет!"));
+        assert!(translated.contains("\r\n\r\n2\r\n00:00:04,000 --> 00:00:05,000\r\n<i>Хорошо.</i>"));
+    }
+
+    #[test]
+    fn test_vtt_blocks() {
+        let content = "WEBVTT - Sample\n\n\
+NOTE translated by tagent\n\n\
+STYLE\n::cue { color: yellow }\n\n\
+intro\n00:01.000 --> 00:02.500 align:start line:90%\nWelcome\n\n\
+00:03.000 --> 00:04.000\nBye\n";
+
+        let segments = split(content);
+        assert_eq!(texts(&segments), vec!["Welcome", "Bye"]);
+        assert_eq!(join(&segments, &[]), content);
+
+        let translated = join(
+            &segments,
+            &[
+                bilingual("Welcome", "Willkommen", line_ending(content)),
+                bilingual("Bye", "Tschüss", line_ending(content)),
+            ],
+        );
+        assert!(translated.contains("line:90%\nWelcome\nWillkommen\n\n00:03.000"));
+        assert!(translated.ends_with("Bye\nTschüss\n"));
+    }
+
+    #[test]
+    fn test_timing_line() {
+        assert!(is_timing_line("00:00:01,000 --> 00:00:03,500\n"));
+        assert!(is_timing_line("01:02.003 --> 01:04.000 position:10%"));
+        assert!(!is_timing_line("Text with an arrow --> inside"));
+        assert!(!is_timing_line("42"));
+    }
+}
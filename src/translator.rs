@@ -1,13 +1,20 @@
 use crate::clipboard::ClipboardManager;
-use crate::config::ConfigManager;
-use crate::providers::{self, TranslationProvider};
+use crate::config::{Config, ConfigManager};
+use crate::detect;
+use crate::history::{self, HistoryEntry, HistoryMode};
+use crate::languages;
+use crate::part_of_speech::PartOfSpeechLabels;
+use crate::protect;
+use crate::providers::{self, DictionaryEntry, Translation, TranslationProvider};
+use crate::vocabulary;
 use crate::window::WindowManager;
-use chrono::{DateTime, Utc};
 use colored::Colorize;
 use std::error::Error;
-use std::fs::OpenOptions;
 use std::io::{self, Write};
 use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+const MAX_EXAMPLES: usize = 3; // Usage examples shown under a dictionary entry
 
 #[derive(Clone)]
 pub struct Translator {
@@ -16,17 +23,30 @@ pub struct Translator {
     config_manager: Arc<ConfigManager>,
     window_manager: Arc<WindowManager>,
     stored_foreground_window: Arc<std::sync::Mutex<Option<windows::Win32::Foundation::HWND>>>,
+    pos_labels: Arc<PartOfSpeechLabels>, // Localized part of speech labels
 }
 
 impl Translator {
     pub fn new() -> Result<Self, Box<dyn Error>> {
         let config_path = ConfigManager::get_default_config_path()?;
         let config_manager = Arc::new(ConfigManager::new(config_path.to_string_lossy().as_ref())?);
+        Self::with_config_manager(config_manager)
+    }
+
+    /// Create translator sharing an existing configuration (e.g. with CLI overrides)
+    pub fn with_config_manager(config_manager: Arc<ConfigManager>) -> Result<Self, Box<dyn Error>> {
         let window_manager = Arc::new(WindowManager::new()?);
 
         // Create translation provider based on config
         let config = config_manager.get_config();
-        let provider = providers::create_provider(&config.translate_provider)?;
+        let provider = providers::create_provider(&config.translate_provider, &config)?;
+
+        // A broken user labels file is reported, the embedded labels are used instead
+        let pos_labels = PartOfSpeechLabels::load(&ConfigManager::get_part_of_speech_path(&config))
+            .unwrap_or_else(|e| {
+                eprintln!("Part of speech labels error: {}", e);
+                PartOfSpeechLabels::embedded()
+            });
 
         Ok(Self {
             provider: Arc::new(provider),
@@ -34,41 +54,10 @@ impl Translator {
             config_manager,
             window_manager,
             stored_foreground_window: Arc::new(std::sync::Mutex::new(None)),
+            pos_labels: Arc::new(pos_labels),
         })
     }
 
-    /// Save translation history to file in multi-line format
-    fn save_translation_history(
-        &self,
-        original: &str,
-        translated: &str,
-        source_lang: &str,
-        target_lang: &str,
-        config: &crate::config::Config,
-    ) -> Result<(), Box<dyn Error>> {
-        if !config.save_translation_history {
-            return Ok(()); // История отключена
-        }
-
-        let timestamp: DateTime<Utc> = Utc::now();
-        let formatted_time = timestamp.format("%Y-%m-%d %H:%M:%S UTC");
-
-        let entry = format!(
-            "[{}] {} -> {}\nIN:  {}\nOUT: {}\n---\n\n",
-            formatted_time, source_lang, target_lang, original, translated
-        );
-
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&config.history_file)?;
-
-        file.write_all(entry.as_bytes())?;
-        file.flush()?; // Принудительно записываем на диск
-
-        Ok(())
-    }
-
     /// Check if text is a single word (no spaces, punctuation at edges allowed)
     fn is_single_word(&self, text: &str) -> bool {
         let cleaned = text.trim_matches(|c: char| !c.is_alphabetic());
@@ -131,27 +120,28 @@ impl Translator {
             }
         }
 
-        let (source_code, target_code) = self.config_manager.get_language_codes();
+        let (source_code, target_codes) = self.target_codes_for(&original_text).await;
+        let target_code = target_codes.first().cloned().unwrap_or_default();
 
-        // Check if it's a single word and dictionary feature is enabled
-        if config.show_dictionary && self.is_single_word(&original_text) {
+        if target_codes.len() > 1 {
+            // Several target languages: one translation block per language, no dictionary
+            self.perform_multi_translation(&original_text, &source_code, &target_codes, &config)
+                .await?;
+        } else if config.show_dictionary && self.is_single_word(&original_text) {
+            // Single word with dictionary feature enabled
             match self
                 .get_dictionary_entry(&original_text, &source_code, &target_code)
                 .await
             {
-                Ok(dictionary_info) => {
+                Ok(dictionary) => {
+                    let dictionary_info = dictionary.text;
+
                     // Clear any existing prompt and print on new line
                     print!("\r");
                     io::stdout().flush().ok();
 
                     // Show the original text (source word)
-                    let source_display = if source_code == "auto" {
-                        "Auto".to_string()
-                    } else {
-                        config.source_language.clone()
-                    };
-
-                    let source_label = format!("[{}]: ", source_display);
+                    let source_label = self.source_label(&original_text, &source_code, &config);
 
                     // Use source prompt color for source language label
                     if let Some(color) = ConfigManager::parse_color(&config.source_prompt_color) {
@@ -170,6 +160,9 @@ impl Translator {
                         print!("{}", dict_label);
                     }
                     println!("{}", dictionary_info);
+                    if let Some(note) = self.fallback_note(&dictionary.provider) {
+                        println!("{}", note.dimmed());
+                    }
                     println!(); // Add empty line after dictionary entry in GUI mode
 
                     if let Err(e) = self.copy_to_clipboard_if_enabled(&dictionary_info, &config) {
@@ -177,13 +170,15 @@ impl Translator {
                     }
 
                     // Сохраняем словарную статью в историю
-                    if let Err(e) = self.save_translation_history(
-                        &original_text,
-                        &dictionary_info,
+                    let entry = HistoryEntry::new(
+                        HistoryMode::Hotkey,
+                        &dictionary.provider,
                         &source_code,
                         &target_code,
-                        &config,
-                    ) {
+                        &original_text,
+                        &dictionary_info,
+                    );
+                    if let Err(e) = history::save(&config, &entry) {
                         println!("History save error: {}", e);
                     }
 
@@ -230,13 +225,7 @@ impl Translator {
         io::stdout().flush().ok();
 
         // Show source language info with colored prompt
-        let source_display = if source_code == "auto" {
-            "Auto".to_string()
-        } else {
-            config.source_language.clone()
-        };
-
-        let source_label = format!("[{}]: ", source_display);
+        let source_label = self.source_label(text, source_code, config);
 
         // Use source prompt color for all source language labels
         if let Some(color) = ConfigManager::parse_color(&config.source_prompt_color) {
@@ -250,7 +239,7 @@ impl Translator {
         if source_code != "auto" && !self.is_expected_language(text, source_code) {
             println!(
                 "Text does not appear to be in {} language",
-                config.source_language
+                ConfigManager::language_name(config, source_code)
             );
             return Ok(());
         }
@@ -259,15 +248,24 @@ impl Translator {
             .translate_text_internal(text, source_code, target_code)
             .await
         {
-            Ok(translated_text) => {
+            Ok(translation) => {
+                let translated_text = translation.text;
+
                 // Print colored translation label
-                let trans_label = format!("[{}]: ", config.target_language);
+                let trans_label =
+                    format!("[{}]: ", ConfigManager::language_name(config, target_code));
                 if let Some(color) = ConfigManager::parse_color(&config.target_prompt_color) {
                     print!("{}", trans_label.color(color));
                 } else {
                     print!("{}", trans_label);
                 }
                 println!("{}", translated_text);
+                if let Some(note) = self.fallback_note(&translation.provider) {
+                    println!("{}", note.dimmed());
+                }
+                for warning in &translation.warnings {
+                    println!("{}", format!("Warning: {}", warning).dimmed());
+                }
                 println!(); // Add empty line after translation result
 
                 if let Err(e) = self.copy_to_clipboard_if_enabled(&translated_text, config) {
@@ -275,13 +273,15 @@ impl Translator {
                 }
 
                 // Сохраняем перевод в историю
-                if let Err(e) = self.save_translation_history(
-                    text,
-                    &translated_text,
+                let entry = HistoryEntry::new(
+                    HistoryMode::Hotkey,
+                    &translation.provider,
                     source_code,
                     target_code,
-                    config,
-                ) {
+                    text,
+                    &translated_text,
+                );
+                if let Err(e) = history::save(config, &entry) {
                     println!("History save error: {}", e);
                 }
 
@@ -302,13 +302,126 @@ impl Translator {
         Ok(())
     }
 
+    /// Translate into several target languages and print one block per language
+    async fn perform_multi_translation(
+        &self,
+        text: &str,
+        source_code: &str,
+        target_codes: &[String],
+        config: &Config,
+    ) -> Result<(), Box<dyn Error>> {
+        // Clear any existing prompt and move to new line
+        print!("\r");
+        io::stdout().flush().ok();
+
+        let source_label = self.source_label(text, source_code, config);
+        if let Some(color) = ConfigManager::parse_color(&config.source_prompt_color) {
+            print!("{}", source_label.color(color));
+        } else {
+            print!("{}", source_label);
+        }
+        println!("{}", text);
+
+        if source_code != "auto" && !self.is_expected_language(text, source_code) {
+            println!(
+                "Text does not appear to be in {} language",
+                ConfigManager::language_name(config, source_code)
+            );
+            return Ok(());
+        }
+
+        let results = self
+            .translate_to_targets(text, source_code, target_codes)
+            .await;
+        self.print_target_translations(text, source_code, &results, config, HistoryMode::Hotkey);
+        println!(); // Add empty line after translation result
+
+        // Show source language prompt after hotkey translation
+        let source_prompt = format!("[{}]: ", config.source_language);
+        if let Some(color) = ConfigManager::parse_color(&config.source_prompt_color) {
+            print!("{}", source_prompt.color(color));
+        } else {
+            print!("{}", source_prompt);
+        }
+        io::stdout().flush().ok();
+
+        Ok(())
+    }
+
+    /// Print one colored "[Language]: " block per target language
+    /// Clipboard and history follow the ClipboardTargets and HistoryTargets settings
+    pub fn print_target_translations(
+        &self,
+        text: &str,
+        source_code: &str,
+        results: &[(String, Result<Translation, String>)],
+        config: &Config,
+        mode: HistoryMode,
+    ) {
+        let target_codes: Vec<String> = results.iter().map(|(code, _)| code.clone()).collect();
+        let mut clipboard_blocks: Vec<(String, String)> = Vec::new();
+
+        for (target_code, result) in results {
+            let label = format!("[{}]: ", ConfigManager::language_name(config, target_code));
+            if let Some(color) = ConfigManager::parse_color(&config.target_prompt_color) {
+                print!("{}", label.color(color));
+            } else {
+                print!("{}", label);
+            }
+
+            let translation = match result {
+                Ok(translation) => translation,
+                Err(e) => {
+                    println!("Translation error: {}", e);
+                    continue;
+                }
+            };
+            println!("{}", translation.text);
+            if let Some(note) = self.fallback_note(&translation.provider) {
+                println!("{}", note.dimmed());
+            }
+            for warning in &translation.warnings {
+                println!("{}", format!("Warning: {}", warning).dimmed());
+            }
+
+            let selected = |setting: &str| {
+                ConfigManager::is_target_selected(setting, &target_codes, target_code)
+            };
+
+            if selected(&config.clipboard_targets) {
+                clipboard_blocks.push((label, translation.text.clone()));
+            }
+
+            if selected(&config.history_targets) {
+                let entry = HistoryEntry::new(
+                    mode,
+                    &translation.provider,
+                    source_code,
+                    target_code,
+                    text,
+                    &translation.text,
+                );
+                if let Err(e) = history::save(config, &entry) {
+                    println!("History save error: {}", e);
+                }
+            }
+        }
+
+        if let Some(clipboard_text) = labeled_clipboard_text(&clipboard_blocks) {
+            if let Err(e) = self.copy_to_clipboard_if_enabled(&clipboard_text, config) {
+                println!("Translation clipboard write error: {}", e);
+            }
+        }
+    }
+
     /// Public method for CLI to get dictionary entry (without headers)
+    /// Returns formatted entry and the provider that answered
     pub async fn get_dictionary_entry_public(
         &self,
         word: &str,
         from: &str,
         to: &str,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<Translation, Box<dyn Error>> {
         self.get_dictionary_entry_cli(word, from, to).await
     }
 
@@ -318,23 +431,188 @@ impl Translator {
         text: &str,
         from: &str,
         to: &str,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<Translation, Box<dyn Error>> {
         self.translate_text_internal(text, from, to).await
     }
 
+    /// Translate several texts concurrently, at most max_concurrent requests at a time
+    /// Results keep the input order; any failed text fails the whole batch
+    pub async fn translate_batch(
+        &self,
+        texts: Vec<String>,
+        from: &str,
+        to: &str,
+        max_concurrent: usize,
+    ) -> Result<Vec<Translation>, Box<dyn Error>> {
+        let semaphore = Arc::new(Semaphore::new(max_concurrent.max(1)));
+        let mut tasks = Vec::with_capacity(texts.len());
+
+        for text in texts {
+            let translator = self.clone();
+            let semaphore = semaphore.clone();
+            let (from, to) = (from.to_string(), to.to_string());
+
+            tasks.push(tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await.map_err(|e| e.to_string())?;
+                translator
+                    .translate_text_internal(&text, &from, &to)
+                    .await
+                    .map_err(|e| e.to_string())
+            }));
+        }
+
+        let mut translations = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            translations.push(task.await??);
+        }
+
+        Ok(translations)
+    }
+
+    /// Translate text into several target languages concurrently
+    /// Results keep the order of targets; a failed language does not affect the others
+    pub async fn translate_to_targets(
+        &self,
+        text: &str,
+        from: &str,
+        target_codes: &[String],
+    ) -> Vec<(String, Result<Translation, String>)> {
+        let mut tasks = Vec::with_capacity(target_codes.len());
+
+        for target_code in target_codes {
+            let translator = self.clone();
+            let (text, from, to) = (text.to_string(), from.to_string(), target_code.clone());
+
+            tasks.push(tokio::spawn(async move {
+                translator
+                    .translate_text_internal(&text, &from, &to)
+                    .await
+                    .map_err(|e| e.to_string())
+            }));
+        }
+
+        let mut results = Vec::with_capacity(tasks.len());
+        for (target_code, task) in target_codes.iter().zip(tasks) {
+            let result = task
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|result| result);
+            results.push((target_code.clone(), result));
+        }
+
+        results
+    }
+
+    /// Public method for CLI to list languages supported by the provider
+    pub async fn get_supported_languages(
+        &self,
+    ) -> Result<Vec<providers::LanguageInfo>, Box<dyn Error>> {
+        self.provider.get_supported_languages().await
+    }
+
+    /// Detect language of text, returns language code
+    /// Asks the provider first and falls back to offline detection
+    pub async fn detect_language(&self, text: &str) -> Option<String> {
+        match self.provider.detect_language(text).await {
+            Ok(code) => Some(languages::to_code(&code).to_string()),
+            Err(_) => detect::detect(text).map(String::from),
+        }
+    }
+
+    /// Source and target language codes for text; the first target if there are several
+    pub async fn language_codes_for(&self, text: &str) -> (String, String) {
+        let (source_code, target_codes) = self.target_codes_for(text).await;
+        (
+            source_code,
+            target_codes.into_iter().next().unwrap_or_default(),
+        )
+    }
+
+    /// Source language code and all target language codes for text
+    /// With LanguagePair set, text in the pair's second language is translated into the first one
+    /// and any other text into the second one
+    pub async fn target_codes_for(&self, text: &str) -> (String, Vec<String>) {
+        let Some((first, second)) = self.config_manager.get_language_pair() else {
+            let (source_code, _) = self.config_manager.get_language_codes();
+            return (source_code, self.config_manager.get_target_codes());
+        };
+
+        // Offline detection is enough when the languages use different scripts
+        let detected = if detect::same_script(&first, &second) {
+            self.detect_language(text).await
+        } else {
+            detect::detect(text).map(String::from)
+        };
+        let detected = detected.unwrap_or_default();
+        let detected = detected.split('-').next().unwrap_or_default();
+
+        if detected == second {
+            (second, vec![first])
+        } else if detected == first {
+            (first, vec![second])
+        } else {
+            ("auto".to_string(), vec![second])
+        }
+    }
+
+    /// Get translation provider name
+    pub fn provider_name(&self) -> &str {
+        self.provider.name()
+    }
+
+    /// Note shown when a fallback provider answered instead of the primary one
+    pub fn fallback_note(&self, provider: &str) -> Option<String> {
+        if provider.is_empty() || provider == self.provider.primary_name() {
+            None
+        } else {
+            Some(format!("(via {})", provider))
+        }
+    }
+
     /// Get dictionary entry for CLI (clean output)
     async fn get_dictionary_entry_cli(
         &self,
         word: &str,
         from: &str,
         to: &str,
-    ) -> Result<String, Box<dyn Error>> {
+    ) -> Result<Translation, Box<dyn Error>> {
+        match self.lookup_dictionary_entry(word, from, to).await? {
+            Some(entry) => Ok(Translation {
+                text: self.format_dictionary_entry(&entry, from, to, true),
+                provider: entry.provider,
+                detected_source_lang: None,
+                warnings: Vec::new(),
+                glossary_hits: Vec::new(),
+            }),
+            None => Err("Limited dictionary information available".into()),
+        }
+    }
+
+    /// Get structured dictionary entry and add it to the vocabulary list
+    pub async fn lookup_dictionary_entry(
+        &self,
+        word: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<DictionaryEntry>, Box<dyn Error>> {
         let entry_opt = self.provider.get_dictionary_entry(word, from, to).await?;
 
-        match entry_opt {
-            Some(entry) => Ok(self.format_dictionary_entry(&entry, to, true)),
-            None => Err("Limited dictionary information available".into()),
+        if let Some(entry) = &entry_opt {
+            // Words are stored under their language, not "auto"
+            let source_lang = if from != "auto" {
+                Some(from.to_string())
+            } else if let Some(code) = &entry.detected_source_lang {
+                Some(languages::to_code(code).to_string())
+            } else {
+                self.detect_language(word).await
+            };
+
+            if let Some(source_lang) = source_lang {
+                self.record_vocabulary(entry, &source_lang, to);
+            }
         }
+
+        Ok(entry_opt)
     }
 
     /// Get dictionary entry for a single word (GUI mode)
@@ -343,23 +621,38 @@ impl Translator {
         word: &str,
         from: &str,
         to: &str,
-    ) -> Result<String, Box<dyn Error>> {
-        let entry_opt = self.provider.get_dictionary_entry(word, from, to).await?;
-
-        match entry_opt {
-            Some(entry) => Ok(self.format_dictionary_entry(&entry, to, true)), // Use cli_mode=true to skip word header
+    ) -> Result<Translation, Box<dyn Error>> {
+        match self.lookup_dictionary_entry(word, from, to).await? {
+            Some(entry) => Ok(Translation {
+                text: self.format_dictionary_entry(&entry, from, to, true), // Use cli_mode=true to skip word header
+                provider: entry.provider,
+                detected_source_lang: None,
+                warnings: Vec::new(),
+                glossary_hits: Vec::new(),
+            }),
             None => Err("Limited dictionary information available".into()),
         }
     }
 
+    /// Add dictionary entry to the vocabulary list
+    /// Errors are reported but never interrupt the lookup
+    fn record_vocabulary(&self, entry: &DictionaryEntry, from: &str, to: &str) {
+        let config = self.config_manager.get_config();
+        if let Err(e) = vocabulary::record(&config, entry, from, to) {
+            eprintln!("Vocabulary save error: {}", e);
+        }
+    }
+
     /// Format dictionary entry into string
     /// cli_mode: true for CLI (no word header), false for GUI (with word header)
     fn format_dictionary_entry(
         &self,
-        entry: &crate::providers::DictionaryEntry,
+        entry: &DictionaryEntry,
+        source_lang: &str,
         target_lang: &str,
         cli_mode: bool,
     ) -> String {
+        let config = self.config_manager.get_config();
         let mut result = Vec::new();
 
         // Add the original word at the beginning (only for GUI mode)
@@ -367,145 +660,17 @@ impl Translator {
             result.push(entry.word.clone());
         }
 
-        // Format each part of speech entry
-        for pos_entry in &entry.definitions {
-            let pos_full = self.get_full_part_of_speech(&pos_entry.part_of_speech, target_lang);
-            result.push(pos_full.to_string());
-
-            // Format definitions with synonyms
-            for def in &pos_entry.definitions {
-                if !def.synonyms.is_empty() {
-                    result.push(format!("  {} [{}]", def.text, def.synonyms.join(", ")));
-                } else {
-                    result.push(format!("  {}", def.text));
-                }
-            }
-        }
+        result.extend(dictionary_lines(
+            entry,
+            &self.pos_labels,
+            &config,
+            source_lang,
+            target_lang,
+        ));
 
         result.join("\n")
     }
 
-    /// Get full part of speech name in target language
-    fn get_full_part_of_speech(&self, pos: &str, target_lang: &str) -> &'static str {
-        let pos_lower = pos.to_lowercase();
-
-        match target_lang {
-            "ru" => match pos_lower.as_str() {
-                "noun" | "существительное" => "Существительное",
-                "verb" | "глагол" => "Глагол",
-                "adjective" | "прилагательное" => "Прилагательное",
-                "adverb" | "наречие" => "Наречие",
-                "preposition" | "предлог" => "Предлог",
-                "conjunction" | "союз" => "Союз",
-                "pronoun" | "местоимение" => "Местоимение",
-                "interjection" | "междометие" => "Междометие",
-                "article" | "артикль" => "Артикль",
-                "determiner" | "определитель" => "Определитель",
-                "participle" | "причастие" => "Причастие",
-                _ => "Прочее",
-            },
-            "es" => match pos_lower.as_str() {
-                "noun" => "Sustantivo",
-                "verb" => "Verbo",
-                "adjective" => "Adjetivo",
-                "adverb" => "Adverbio",
-                "preposition" => "Preposición",
-                "conjunction" => "Conjunción",
-                "pronoun" => "Pronombre",
-                "interjection" => "Interjección",
-                "article" => "Artículo",
-                "determiner" => "Determinante",
-                "participle" => "Participio",
-                _ => "Otro",
-            },
-            "fr" => match pos_lower.as_str() {
-                "noun" => "Nom",
-                "verb" => "Verbe",
-                "adjective" => "Adjectif",
-                "adverb" => "Adverbe",
-                "preposition" => "Préposition",
-                "conjunction" => "Conjonction",
-                "pronoun" => "Pronom",
-                "interjection" => "Interjection",
-                "article" => "Article",
-                "determiner" => "Déterminant",
-                "participle" => "Participe",
-                _ => "Autre",
-            },
-            "de" => match pos_lower.as_str() {
-                "noun" => "Substantiv",
-                "verb" => "Verb",
-                "adjective" => "Adjektiv",
-                "adverb" => "Adverb",
-                "preposition" => "Präposition",
-                "conjunction" => "Konjunktion",
-                "pronoun" => "Pronomen",
-                "interjection" => "Interjektion",
-                "article" => "Artikel",
-                "determiner" => "Bestimmungswort",
-                "participle" => "Partizip",
-                _ => "Andere",
-            },
-            "it" => match pos_lower.as_str() {
-                "noun" => "Sostantivo",
-                "verb" => "Verbo",
-                "adjective" => "Aggettivo",
-                "adverb" => "Avverbio",
-                "preposition" => "Preposizione",
-                "conjunction" => "Congiunzione",
-                "pronoun" => "Pronome",
-                "interjection" => "Interiezione",
-                "article" => "Articolo",
-                "determiner" => "Determinante",
-                "participle" => "Participio",
-                _ => "Altro",
-            },
-            "pt" => match pos_lower.as_str() {
-                "noun" => "Substantivo",
-                "verb" => "Verbo",
-                "adjective" => "Adjetivo",
-                "adverb" => "Advérbio",
-                "preposition" => "Preposição",
-                "conjunction" => "Conjunção",
-                "pronoun" => "Pronome",
-                "interjection" => "Interjeição",
-                "article" => "Artigo",
-                "determiner" => "Determinante",
-                "participle" => "Particípio",
-                _ => "Outro",
-            },
-            "zh" => match pos_lower.as_str() {
-                "noun" => "名词",
-                "verb" => "动词",
-                "adjective" => "形容词",
-                "adverb" => "副词",
-                "preposition" => "介词",
-                "conjunction" => "连词",
-                "pronoun" => "代词",
-                "interjection" => "感叹词",
-                "article" => "冠词",
-                "determiner" => "限定词",
-                "participle" => "分词",
-                _ => "其他",
-            },
-            // English fallback (default)
-            _ => match pos_lower.as_str() {
-                "noun" | "существительное" => "Noun",
-                "verb" | "глагол" => "Verb",
-                "adjective" | "прилагательное" => "Adjective",
-                "adverb" | "наречие" => "Adverb",
-                "preposition" | "предлог" => "Preposition",
-                "conjunction" | "союз" => "Conjunction",
-                "pronoun" | "местоимение" => "Pronoun",
-                "interjection" | "междометие" => "Interjection",
-                "article" | "артикль" => "Article",
-                "determiner" | "определитель" => "Determiner",
-                "participle" | "причастие" => "Participle",
-                _ => "Other",
-            },
-        }
-    }
-
     /// Hide terminal window and restore previously active window
     /// Delays hiding if mouse cursor is over the terminal
     async fn hide_terminal_and_restore(&self, delay_seconds: u64) {
@@ -548,53 +713,222 @@ impl Translator {
     }
 
     /// Check if text appears to be in expected language
+    /// Offline detection, so the check costs no request
     fn is_expected_language(&self, text: &str, language_code: &str) -> bool {
-        match language_code {
-            "en" => self.is_english_text(text),
-            "ru" => self.is_russian_text(text),
-            _ => true, // For other languages, assume it's correct
-        }
+        detect::is_language(text, language_code)
     }
 
-    /// Check if text contains English characters
-    fn is_english_text(&self, text: &str) -> bool {
-        let english_chars = text.chars().filter(|c| c.is_alphabetic()).count();
+    /// Source language label for hotkey output, "[Auto: English]: " when detected
+    fn source_label(&self, text: &str, source_code: &str, config: &Config) -> String {
+        if source_code != "auto" {
+            return format!("[{}]: ", ConfigManager::language_name(config, source_code));
+        }
 
-        let total_chars = text.chars().filter(|c| !c.is_whitespace()).count();
+        match detect::detect(text) {
+            Some(code) => format!("[Auto: {}]: ", languages::name(code)),
+            None => "[Auto]: ".to_string(),
+        }
+    }
 
-        if total_chars == 0 {
-            return false;
+    /// Translate text using translation provider
+    /// Tags, placeholders, URLs, emails and glossary terms are masked before the request
+    /// and put back after it; markers lost by the provider are reported in Translation::warnings
+    async fn translate_text_internal(
+        &self,
+        text: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Translation, Box<dyn Error>> {
+        let glossary = self.config_manager.get_glossary(from, to);
+        let (protected, glossary_hits) = protect::protect_with_glossary(text, &glossary);
+        if !protected.has_tokens() {
+            return self.provider.translate_text(text, from, to).await;
         }
 
-        let english_ratio = english_chars as f64 / total_chars as f64;
+        let mut translation = self
+            .provider
+            .translate_text(&protected.text, from, to)
+            .await?;
+        let (restored, problems) = protected.restore(&translation.text);
+        translation.text = restored;
+        translation.warnings.extend(problems);
+        translation.glossary_hits = glossary_hits;
 
-        english_ratio > 0.7 && text.chars().any(|c| c.is_ascii_alphabetic())
+        Ok(translation)
     }
+}
 
-    /// Check if text contains Russian characters
-    fn is_russian_text(&self, text: &str) -> bool {
-        let russian_chars = text
-            .chars()
-            .filter(|c| c.is_alphabetic() && (*c as u32) >= 0x0400 && (*c as u32) <= 0x04FF)
-            .count();
+/// Clipboard text for translations into several languages ("[Language]: " label, text)
+/// A single translation is copied as is, several one per line with their labels
+pub fn labeled_clipboard_text(blocks: &[(String, String)]) -> Option<String> {
+    match blocks {
+        [] => None,
+        [(_, translated)] => Some(translated.clone()),
+        blocks => Some(
+            blocks
+                .iter()
+                .map(|(label, translated)| format!("{}{}", label, translated))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+    }
+}
 
-        let total_chars = text.chars().filter(|c| !c.is_whitespace()).count();
+/// Dictionary entry lines in the [Dictionary] layout
+/// Part of speech labels are in the target language, definitions in the source language
+fn dictionary_lines(
+    entry: &DictionaryEntry,
+    labels: &PartOfSpeechLabels,
+    config: &Config,
+    source_lang: &str,
+    target_lang: &str,
+) -> Vec<String> {
+    let limit = |max: usize| if max == 0 { usize::MAX } else { max };
+    let max_definitions = limit(config.dictionary_max_definitions);
+    let max_synonyms = limit(config.dictionary_max_synonyms);
+    let compact = config.dictionary_layout == "compact";
+    let mut result = Vec::new();
+
+    if config.dictionary_show_translation {
+        if let Some(translation) = &entry.translation {
+            match &entry.translation_transliteration {
+                Some(transliteration) if transliteration != translation => {
+                    result.push(format!("{} ({})", translation, transliteration))
+                }
+                _ => result.push(translation.clone()),
+            }
+        }
+    }
+
+    if !compact {
+        if let Some(transliteration) = &entry.transliteration {
+            result.push(format!("[{}]", transliteration));
+        }
+    }
+
+    // Format each part of speech entry
+    for pos_entry in &entry.definitions {
+        let pos = labels.label(labels.normalize(&pos_entry.part_of_speech), target_lang);
+        let definitions = pos_entry.definitions.iter().take(max_definitions);
 
-        if total_chars == 0 {
-            return false;
+        if compact {
+            let texts: Vec<&str> = definitions.map(|def| def.text.as_str()).collect();
+            result.push(format!("{}: {}", pos, texts.join(", ")));
+            continue;
         }
 
-        let russian_ratio = russian_chars as f64 / total_chars as f64;
-        russian_ratio > 0.3 // Lower threshold for Russian as it might contain English words
+        result.push(pos.to_string());
+
+        // Format definitions with synonyms and how often the word means this
+        for def in definitions {
+            let mut line = format!("  {}", def.text);
+            if !def.synonyms.is_empty() {
+                let synonyms: Vec<&str> = def
+                    .synonyms
+                    .iter()
+                    .take(max_synonyms)
+                    .map(String::as_str)
+                    .collect();
+                line.push_str(&format!(" [{}]", synonyms.join(", ")));
+            }
+            if let Some(frequency) = def.frequency {
+                line.push_str(&format!(" ({})", frequency.name()));
+            }
+            result.push(line);
+        }
     }
 
-    /// Translate text using translation provider
-    async fn translate_text_internal(
-        &self,
-        text: &str,
-        from: &str,
-        to: &str,
-    ) -> Result<String, Box<dyn Error>> {
-        self.provider.translate_text(text, from, to).await
+    if compact {
+        return result;
+    }
+
+    // Meanings explained in the source language
+    if !entry.source_definitions.is_empty() {
+        result.push("Definitions:".to_string());
+    }
+    for pos_entry in &entry.source_definitions {
+        let pos = labels.normalize(&pos_entry.part_of_speech);
+        result.push(format!("  {}", labels.label(pos, source_lang)));
+
+        for def in pos_entry.definitions.iter().take(max_definitions) {
+            result.push(format!("    - {}", def.text));
+            if let Some(example) = &def.example {
+                result.push(format!("      \"{}\"", example));
+            }
+        }
+    }
+
+    if !entry.examples.is_empty() {
+        result.push("Examples:".to_string());
+    }
+    for example in entry.examples.iter().take(MAX_EXAMPLES) {
+        result.push(format!("  - {}", example));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{Definition, PartOfSpeechEntry};
+
+    fn entry() -> DictionaryEntry {
+        let definition = |text: &str, synonyms: &[&str]| Definition {
+            text: text.to_string(),
+            synonyms: synonyms.iter().map(|s| s.to_string()).collect(),
+            frequency: None,
+        };
+
+        DictionaryEntry {
+            word: "cat".to_string(),
+            definitions: vec![PartOfSpeechEntry {
+                part_of_speech: "noun".to_string(),
+                definitions: vec![
+                    definition("кот", &["cat", "tomcat"]),
+                    definition("кошка", &["cat", "pussy"]),
+                    definition("котик", &["kitty"]),
+                ],
+            }],
+            provider: "Google Translate".to_string(),
+            translation: Some("кот".to_string()),
+            translation_transliteration: Some("kot".to_string()),
+            transliteration: Some("kat".to_string()),
+            examples: vec!["the cat sat on the mat".to_string()],
+            ..DictionaryEntry::default()
+        }
+    }
+
+    #[test]
+    fn test_dictionary_layouts() {
+        let labels = PartOfSpeechLabels::embedded();
+
+        let expanded = Config {
+            dictionary_max_definitions: 2,
+            dictionary_max_synonyms: 1,
+            ..Config::default()
+        };
+        assert_eq!(
+            dictionary_lines(&entry(), &labels, &expanded, "en", "ru"),
+            [
+                "[kat]",
+                "Существительное",
+                "  кот [cat]",
+                "  кошка [cat]",
+                "Examples:",
+                "  - the cat sat on the mat"
+            ]
+        );
+
+        let compact = Config {
+            dictionary_layout: "compact".to_string(),
+            dictionary_max_definitions: 0,
+            dictionary_show_translation: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            dictionary_lines(&entry(), &labels, &compact, "en", "ru"),
+            ["кот (kot)", "Существительное: кот, кошка, котик"]
+        );
     }
 }
@@ -0,0 +1,366 @@
+use crate::config::{Config, ConfigManager};
+use crate::providers::{Definition, DictionaryEntry, PartOfSpeechEntry};
+use crate::quiz::ReviewState;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Word collected from dictionary lookups
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VocabularyWord {
+    pub word: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    pub definitions: Vec<PartOfSpeechEntry>,
+    pub lookups: u32, // How many times the word was looked up
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+    #[serde(default)]
+    pub review: ReviewState, // Spaced repetition state for /quiz
+}
+
+impl VocabularyWord {
+    /// Same word and language pair (case-insensitive)
+    fn is_same(&self, word: &str, source_lang: &str, target_lang: &str) -> bool {
+        self.word.to_lowercase() == word.to_lowercase()
+            && self.source_lang == source_lang
+            && self.target_lang == target_lang
+    }
+
+    /// Merge definitions from a new lookup, keeping existing order
+    fn merge(&mut self, definitions: &[PartOfSpeechEntry]) {
+        for pos_entry in definitions {
+            let existing = self
+                .definitions
+                .iter_mut()
+                .find(|e| e.part_of_speech == pos_entry.part_of_speech);
+
+            let existing = match existing {
+                Some(existing) => existing,
+                None => {
+                    self.definitions.push(pos_entry.clone());
+                    continue;
+                }
+            };
+
+            for def in &pos_entry.definitions {
+                match existing.definitions.iter_mut().find(|d| d.text == def.text) {
+                    Some(existing_def) => {
+                        for synonym in &def.synonyms {
+                            if !existing_def.synonyms.contains(synonym) {
+                                existing_def.synonyms.push(synonym.clone());
+                            }
+                        }
+                    }
+                    None => existing.definitions.push(def.clone()),
+                }
+            }
+        }
+    }
+}
+
+/// Vocabulary list stored as JSON next to the configuration file
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Vocabulary {
+    pub words: Vec<VocabularyWord>,
+}
+
+/// Export file format
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,      // Anki-compatible, comma separated
+    Tsv,      // Anki-compatible, tab separated
+    Markdown, // Readable word list
+}
+
+impl ExportFormat {
+    pub fn parse(value: &str) -> Result<Self, Box<dyn Error>> {
+        match value.trim().to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "tsv" | "txt" | "anki" => Ok(ExportFormat::Tsv),
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            _ => Err(format!("Unknown export format: {} (expected csv, tsv or md)", value).into()),
+        }
+    }
+
+    /// Guess format from output file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| Self::parse(ext).ok())
+    }
+}
+
+impl Vocabulary {
+    /// Load vocabulary, a missing file gives an empty list
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e).into())
+    }
+
+    /// Write through a temporary file so a crash never leaves the list half-written
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let content = serde_json::to_string_pretty(self)?;
+
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, content)?;
+        fs::rename(&temp_path, path)?;
+
+        Ok(())
+    }
+
+    /// Find word by text and language pair
+    pub fn find_mut(
+        &mut self,
+        word: &str,
+        source_lang: &str,
+        target_lang: &str,
+    ) -> Option<&mut VocabularyWord> {
+        self.words
+            .iter_mut()
+            .find(|w| w.is_same(word, source_lang, target_lang))
+    }
+
+    /// Add dictionary entry, merging it into an existing word
+    pub fn add(&mut self, entry: &DictionaryEntry, source_lang: &str, target_lang: &str) {
+        let now = Utc::now();
+
+        match self.find_mut(&entry.word, source_lang, target_lang) {
+            Some(existing) => {
+                existing.merge(&entry.definitions);
+                existing.lookups += 1;
+                existing.last_seen = now;
+            }
+            None => self.words.push(VocabularyWord {
+                word: entry.word.clone(),
+                source_lang: source_lang.to_string(),
+                target_lang: target_lang.to_string(),
+                definitions: entry.definitions.clone(),
+                lookups: 1,
+                first_seen: now,
+                last_seen: now,
+                review: ReviewState::default(),
+            }),
+        }
+    }
+
+    /// Export words in the given format
+    pub fn export(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Csv => self.export_anki(','),
+            ExportFormat::Tsv => self.export_anki('\t'),
+            ExportFormat::Markdown => self.export_markdown(),
+        }
+    }
+
+    /// Anki import format: front, back (HTML), tags
+    /// Header lines are understood by Anki 2.1.54+ and skipped as comments by older versions
+    fn export_anki(&self, separator: char) -> String {
+        let mut lines = vec![
+            format!(
+                "#separator:{}",
+                if separator == '\t' { "tab" } else { "comma" }
+            ),
+            "#html:true".to_string(),
+            "#tags column:3".to_string(),
+        ];
+
+        for word in &self.words {
+            let back = word
+                .definitions
+                .iter()
+                .map(|pos_entry| {
+                    let definitions = pos_entry
+                        .definitions
+                        .iter()
+                        .map(format_definition)
+                        .collect::<Vec<_>>()
+                        .join("<br>");
+                    format!(
+                        "<b>{}</b><br>{}",
+                        capitalize(&pos_entry.part_of_speech),
+                        definitions
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("<br>");
+
+            let tags = format!("tagent {}-{}", word.source_lang, word.target_lang);
+
+            let fields = [word.word.as_str(), back.as_str(), tags.as_str()];
+            let line = fields
+                .iter()
+                .map(|field| escape_field(field, separator))
+                .collect::<Vec<_>>()
+                .join(&separator.to_string());
+            lines.push(line);
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    fn export_markdown(&self) -> String {
+        let mut result = vec!["# Vocabulary".to_string(), String::new()];
+
+        for word in &self.words {
+            result.push(format!(
+                "## {} ({} -> {})",
+                word.word, word.source_lang, word.target_lang
+            ));
+            result.push(String::new());
+
+            for pos_entry in &word.definitions {
+                result.push(format!("**{}**", capitalize(&pos_entry.part_of_speech)));
+                result.push(String::new());
+                for def in &pos_entry.definitions {
+                    result.push(format!("- {}", format_definition(def)));
+                }
+                result.push(String::new());
+            }
+        }
+
+        result.join("\n")
+    }
+}
+
+/// Add dictionary lookup result to the vocabulary list if collection is enabled
+/// source_lang must be the language of the word; "auto" is not recorded
+pub fn record(
+    config: &Config,
+    entry: &DictionaryEntry,
+    source_lang: &str,
+    target_lang: &str,
+) -> Result<(), Box<dyn Error>> {
+    if !config.collect_vocabulary || entry.definitions.is_empty() || source_lang == "auto" {
+        return Ok(());
+    }
+
+    let path = ConfigManager::get_vocabulary_path(config);
+    let mut vocabulary = Vocabulary::load(&path)?;
+    vocabulary.add(entry, source_lang, target_lang);
+    vocabulary.save(&path)
+}
+
+/// "кот [кошка, котенок]" in the same style as terminal output
+pub fn format_definition(def: &Definition) -> String {
+    if def.synonyms.is_empty() {
+        def.text.clone()
+    } else {
+        format!("{} [{}]", def.text, def.synonyms.join(", "))
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Quote field for CSV/TSV when it contains separator, quotes or line breaks
+fn escape_field(field: &str, separator: char) -> String {
+    if field.contains(separator) || field.contains('"') || field.contains('\n') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(word: &str, pos: &str, text: &str, synonyms: &[&str]) -> DictionaryEntry {
+        DictionaryEntry {
+            word: word.to_string(),
+            definitions: vec![PartOfSpeechEntry {
+                part_of_speech: pos.to_string(),
+                definitions: vec![Definition {
+                    text: text.to_string(),
+                    synonyms: synonyms.iter().map(|s| s.to_string()).collect(),
+                    frequency: None,
+                }],
+            }],
+            provider: "Google Translate".to_string(),
+            ..DictionaryEntry::default()
+        }
+    }
+
+    #[test]
+    fn test_duplicate_words_are_merged() {
+        let mut vocabulary = Vocabulary::default();
+        vocabulary.add(&entry("cat", "noun", "кот", &["кошка"]), "en", "ru");
+        vocabulary.add(&entry("Cat", "noun", "кот", &["котенок"]), "en", "ru");
+        vocabulary.add(&entry("cat", "verb", "травить", &[]), "en", "ru");
+        vocabulary.add(&entry("cat", "noun", "Katze", &[]), "en", "de");
+
+        assert_eq!(vocabulary.words.len(), 2);
+
+        let cat = &vocabulary.words[0];
+        assert_eq!(cat.lookups, 3);
+        assert_eq!(cat.definitions.len(), 2);
+        assert_eq!(
+            cat.definitions[0].definitions[0].synonyms,
+            vec!["кошка", "котенок"]
+        );
+    }
+
+    #[test]
+    fn test_save_replaces_file() {
+        let path =
+            std::env::temp_dir().join(format!("tagent_vocabulary_{}.json", std::process::id()));
+        let mut vocabulary = Vocabulary::default();
+        vocabulary.add(&entry("cat", "noun", "кот", &[]), "en", "ru");
+        vocabulary.save(&path).unwrap();
+        vocabulary.add(&entry("dog", "noun", "собака", &[]), "en", "ru");
+        vocabulary.save(&path).unwrap();
+
+        assert_eq!(Vocabulary::load(&path).unwrap().words.len(), 2);
+        assert!(!path.with_extension("tmp").exists());
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_export_formats() {
+        let mut vocabulary = Vocabulary::default();
+        vocabulary.add(
+            &entry("cat", "noun", "кот", &["кошка", "котенок"]),
+            "en",
+            "ru",
+        );
+
+        let csv = vocabulary.export(ExportFormat::Csv);
+        assert!(csv.starts_with("#separator:comma\n#html:true\n#tags column:3\n"));
+        assert!(csv.contains("cat,\"<b>Noun</b><br>кот [кошка, котенок]\",tagent en-ru"));
+
+        let tsv = vocabulary.export(ExportFormat::Tsv);
+        assert!(tsv.contains("cat\t<b>Noun</b><br>кот [кошка, котенок]\ttagent en-ru"));
+
+        let markdown = vocabulary.export(ExportFormat::Markdown);
+        assert!(markdown.contains("## cat (en -> ru)"));
+        assert!(markdown.contains("**Noun**"));
+        assert!(markdown.contains("- кот [кошка, котенок]"));
+    }
+
+    #[test]
+    fn test_export_format_from_path() {
+        assert_eq!(
+            ExportFormat::from_path(Path::new("words.csv")),
+            Some(ExportFormat::Csv)
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("words.md")),
+            Some(ExportFormat::Markdown)
+        );
+        assert_eq!(ExportFormat::from_path(Path::new("words")), None);
+    }
+}